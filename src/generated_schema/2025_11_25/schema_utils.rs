@@ -59,6 +59,44 @@ fn detect_message_type(value: &serde_json::Value) -> MessageTypes {
     MessageTypes::Request
 }
 
+/// Detects the message type from a JSON-RPC payload, rejecting payloads that match none of the known shapes.
+///
+/// Unlike the internal detection used during deserialization, which falls back to `Request`,
+/// this returns an `invalid_request` error for input such as `{}` or an object carrying only an `id`,
+/// allowing transports to reject malformed messages early.
+pub fn try_detect_message_type(value: &Value) -> result::Result<MessageTypes, RpcError> {
+    let id_field = value.get("id");
+    let method_field = value.get("method");
+    let result_field = value.get("result");
+
+    if value.get("error").is_some() {
+        return Ok(MessageTypes::Error);
+    }
+
+    if id_field.is_some() {
+        if result_field.is_some() && method_field.is_none() {
+            return Ok(MessageTypes::Response);
+        } else if method_field.is_some() {
+            return Ok(MessageTypes::Request);
+        }
+    } else if method_field.is_some() {
+        return Ok(MessageTypes::Notification);
+    }
+
+    Err(RpcError::invalid_request()
+        .with_message("Payload is not a valid JSON-RPC request, notification, response or error.".to_string()))
+}
+
+/// Removes an explicit `"error": null` member, which some peers send alongside `result`,
+/// so that the payload is recognized as a response rather than failing as a malformed error.
+fn drop_null_error(value: &mut Value) {
+    if let Some(object) = value.as_object_mut() {
+        if object.get("error").is_some_and(Value::is_null) {
+            object.remove("error");
+        }
+    }
+}
+
 /// Represents a generic MCP (Model Context Protocol) message.
 /// This trait defines methods to classify and extract information from messages.
 pub trait RpcMessage: McpMessage {
@@ -134,6 +172,7 @@ impl Hash for RequestId {
     }
 }
 
+/// Formats the id as its raw value, with string ids printed unquoted (e.g. `42` or `abc-1`).
 impl core::fmt::Display for RequestId {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         match *self {
@@ -142,13 +181,36 @@ impl core::fmt::Display for RequestId {
         }
     }
 }
+
+impl RequestId {
+    /// Returns the id with numeric strings such as `"42"` converted to their integer form.
+    ///
+    /// JSON-RPC treats `"42"` and `42` as distinct ids; this is a deliberate deviation for
+    /// correlating responses from lenient peers. Only canonical decimal strings are converted,
+    /// so `"042"` or `"+42"` stay strings.
+    pub fn normalized(&self) -> RequestId {
+        if let RequestId::String(s) = self {
+            if let Ok(i) = s.parse::<i64>() {
+                if i.to_string() == *s {
+                    return RequestId::Integer(i);
+                }
+            }
+        }
+        self.clone()
+    }
+
+    /// Compares two ids after [`normalizing`](Self::normalized) them, so `"42"` equals `42`.
+    pub fn loose_eq(&self, other: &RequestId) -> bool {
+        self.normalized() == other.normalized()
+    }
+}
 //*******************//
 //** ClientMessage **//
 //*******************//
 
 /// "Similar to JsonrpcMessage, but with the variants restricted to client-side messages."
 /// ClientMessage represents a message sent by an MCP Client and received by an MCP Server.
-#[derive(::serde::Deserialize, ::serde::Serialize, Clone, Debug)]
+#[derive(::serde::Serialize, Clone, Debug)]
 #[serde(untagged)]
 pub enum ClientMessage {
     Request(ClientJsonrpcRequest),
@@ -157,6 +219,38 @@ pub enum ClientMessage {
     Error(JsonrpcErrorResponse),
 }
 
+/// Untagged representation `ClientMessage` deserializes through, once the payload has been normalized.
+#[derive(::serde::Deserialize)]
+#[serde(untagged, rename = "ClientMessage")]
+enum ClientMessageRepr {
+    Request(ClientJsonrpcRequest),
+    Notification(ClientJsonrpcNotification),
+    Response(ClientJsonrpcResponse),
+    Error(JsonrpcErrorResponse),
+}
+
+impl<'de> ::serde::Deserialize<'de> for ClientMessage {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: ::serde::Deserializer<'de>,
+    {
+        let mut value = <Value as ::serde::Deserialize>::deserialize(deserializer)?;
+        drop_null_error(&mut value);
+        let message = <ClientMessageRepr as ::serde::Deserialize>::deserialize(value).map_err(::serde::de::Error::custom)?;
+        Ok(match message {
+            ClientMessageRepr::Request(message) => ClientMessage::Request(message),
+            ClientMessageRepr::Notification(message) => ClientMessage::Notification(message),
+            ClientMessageRepr::Response(message) => ClientMessage::Response(message),
+            ClientMessageRepr::Error(message) => ClientMessage::Error(message),
+        })
+    }
+}
+
+/// A reference-counted `ClientMessage`, cheap to clone when fanning a message out to many subscribers,
+/// e.g. through a broadcast channel. Create one with `SharedClientMessage::from(message)` or `message.into()`;
+/// it serializes exactly like the inner message.
+pub type SharedClientMessage = std::sync::Arc<ClientMessage>;
+
 impl ClientMessage {
     /// Converts the current message into a `ClientJsonrpcResponse` if it's of the correct type.
     ///
@@ -254,6 +348,14 @@ impl ClientMessage {
             Self::Notification(ClientJsonrpcNotification::InitializedNotification(_))
         )
     }
+
+    /// Returns the method name (if any) and whether a request id is present, in a single call.
+    ///
+    /// Handy as a key for dispatch tables: requests yield `(Some(method), true)`,
+    /// notifications `(Some(method), false)` and responses `(None, true)`.
+    pub fn dispatch_key(&self) -> (Option<&str>, bool) {
+        (self.method(), self.request_id().is_some())
+    }
 }
 
 impl From<ClientJsonrpcNotification> for ClientMessage {
@@ -467,6 +569,39 @@ impl ClientJsonrpcRequest {
         }
     }
 
+    fn request_id_mut(&mut self) -> &mut RequestId {
+        match self {
+            ClientJsonrpcRequest::InitializeRequest(request) => &mut request.id,
+            ClientJsonrpcRequest::PingRequest(request) => &mut request.id,
+            ClientJsonrpcRequest::ListResourcesRequest(request) => &mut request.id,
+            ClientJsonrpcRequest::ListResourceTemplatesRequest(request) => &mut request.id,
+            ClientJsonrpcRequest::ReadResourceRequest(request) => &mut request.id,
+            ClientJsonrpcRequest::SubscribeRequest(request) => &mut request.id,
+            ClientJsonrpcRequest::UnsubscribeRequest(request) => &mut request.id,
+            ClientJsonrpcRequest::ListPromptsRequest(request) => &mut request.id,
+            ClientJsonrpcRequest::GetPromptRequest(request) => &mut request.id,
+            ClientJsonrpcRequest::ListToolsRequest(request) => &mut request.id,
+            ClientJsonrpcRequest::CallToolRequest(request) => &mut request.id,
+            ClientJsonrpcRequest::GetTaskRequest(request) => &mut request.id,
+            ClientJsonrpcRequest::GetTaskPayloadRequest(request) => &mut request.id,
+            ClientJsonrpcRequest::CancelTaskRequest(request) => &mut request.id,
+            ClientJsonrpcRequest::ListTasksRequest(request) => &mut request.id,
+            ClientJsonrpcRequest::SetLevelRequest(request) => &mut request.id,
+            ClientJsonrpcRequest::CompleteRequest(request) => &mut request.id,
+            ClientJsonrpcRequest::CustomRequest(request) => &mut request.id,
+        }
+    }
+
+    /// Returns the id of the request, same as [`Self::request_id`].
+    pub fn id(&self) -> &RequestId {
+        self.request_id()
+    }
+
+    /// Returns an owned copy of the request id, convenient for moving into async tasks.
+    pub fn clone_id(&self) -> RequestId {
+        self.request_id().clone()
+    }
+
     pub fn is_task_augmented(&self) -> bool {
         if let ClientJsonrpcRequest::CallToolRequest(call_tool_request) = self {
             call_tool_request.is_task_augmented()
@@ -497,6 +632,32 @@ impl ClientJsonrpcRequest {
             ClientJsonrpcRequest::CustomRequest(request) => request.method.as_str(),
         }
     }
+
+    /// Returns the `params` of the request as a `serde_json::Value`, exactly as they are serialized on the wire.
+    /// Returns `Value::Null` when the request has no params.
+    pub fn params_value(&self) -> Value {
+        let value = match self {
+            ClientJsonrpcRequest::InitializeRequest(request) => serde_json::to_value(&request.params),
+            ClientJsonrpcRequest::PingRequest(request) => serde_json::to_value(&request.params),
+            ClientJsonrpcRequest::ListResourcesRequest(request) => serde_json::to_value(&request.params),
+            ClientJsonrpcRequest::ListResourceTemplatesRequest(request) => serde_json::to_value(&request.params),
+            ClientJsonrpcRequest::ReadResourceRequest(request) => serde_json::to_value(&request.params),
+            ClientJsonrpcRequest::SubscribeRequest(request) => serde_json::to_value(&request.params),
+            ClientJsonrpcRequest::UnsubscribeRequest(request) => serde_json::to_value(&request.params),
+            ClientJsonrpcRequest::ListPromptsRequest(request) => serde_json::to_value(&request.params),
+            ClientJsonrpcRequest::GetPromptRequest(request) => serde_json::to_value(&request.params),
+            ClientJsonrpcRequest::ListToolsRequest(request) => serde_json::to_value(&request.params),
+            ClientJsonrpcRequest::CallToolRequest(request) => serde_json::to_value(&request.params),
+            ClientJsonrpcRequest::GetTaskRequest(request) => serde_json::to_value(&request.params),
+            ClientJsonrpcRequest::GetTaskPayloadRequest(request) => serde_json::to_value(&request.params),
+            ClientJsonrpcRequest::CancelTaskRequest(request) => serde_json::to_value(&request.params),
+            ClientJsonrpcRequest::ListTasksRequest(request) => serde_json::to_value(&request.params),
+            ClientJsonrpcRequest::SetLevelRequest(request) => serde_json::to_value(&request.params),
+            ClientJsonrpcRequest::CompleteRequest(request) => serde_json::to_value(&request.params),
+            ClientJsonrpcRequest::CustomRequest(request) => serde_json::to_value(&request.params),
+        };
+        value.unwrap_or(Value::Null)
+    }
 }
 
 impl From<ClientJsonrpcRequest> for RequestFromClient {
@@ -623,10 +784,243 @@ impl RequestFromClient {
             RequestFromClient::CustomRequest(request) => request.method.as_str(),
         }
     }
+
+    /// Returns `true` if the request method equals `method`, regardless of its params.
+    pub fn matches_method(&self, method: &str) -> bool {
+        self.method() == method
+    }
+
+    /// Dispatches to `on_standard` for requests defined by the schema, or to `on_custom` for a `CustomRequest`.
+    pub fn visit<R>(&self, on_standard: impl FnOnce(&Self) -> R, on_custom: impl FnOnce(&CustomRequest) -> R) -> R {
+        match self {
+            RequestFromClient::CustomRequest(request) => on_custom(request),
+            _ => on_standard(self),
+        }
+    }
+
+    /// Builds a typed request from a method name and its params, falling back to `CustomRequest`
+    /// for methods that are not part of the schema.
+    ///
+    /// # Errors
+    /// Returns an `invalid_params` `RpcError` if `method` is known but `params` do not match its schema,
+    /// or if the params of a custom request are not an object.
+    pub fn from_method_and_params(method: &str, params: Value) -> std::result::Result<Self, RpcError> {
+        let request = match method {
+            m if m == InitializeRequest::method_value() => Self::InitializeRequest(client_request_params(method, params)?),
+            m if m == PingRequest::method_value() => Self::PingRequest(client_request_params(method, params)?),
+            m if m == ListResourcesRequest::method_value() => {
+                Self::ListResourcesRequest(client_request_params(method, params)?)
+            }
+            m if m == ListResourceTemplatesRequest::method_value() => {
+                Self::ListResourceTemplatesRequest(client_request_params(method, params)?)
+            }
+            m if m == ReadResourceRequest::method_value() => {
+                Self::ReadResourceRequest(client_request_params(method, params)?)
+            }
+            m if m == SubscribeRequest::method_value() => Self::SubscribeRequest(client_request_params(method, params)?),
+            m if m == UnsubscribeRequest::method_value() => Self::UnsubscribeRequest(client_request_params(method, params)?),
+            m if m == ListPromptsRequest::method_value() => Self::ListPromptsRequest(client_request_params(method, params)?),
+            m if m == GetPromptRequest::method_value() => Self::GetPromptRequest(client_request_params(method, params)?),
+            m if m == ListToolsRequest::method_value() => Self::ListToolsRequest(client_request_params(method, params)?),
+            m if m == CallToolRequest::method_value() => Self::CallToolRequest(client_request_params(method, params)?),
+            m if m == GetTaskRequest::method_value() => Self::GetTaskRequest(client_request_params(method, params)?),
+            m if m == GetTaskPayloadRequest::method_value() => {
+                Self::GetTaskPayloadRequest(client_request_params(method, params)?)
+            }
+            m if m == CancelTaskRequest::method_value() => Self::CancelTaskRequest(client_request_params(method, params)?),
+            m if m == ListTasksRequest::method_value() => Self::ListTasksRequest(client_request_params(method, params)?),
+            m if m == SetLevelRequest::method_value() => Self::SetLevelRequest(client_request_params(method, params)?),
+            m if m == CompleteRequest::method_value() => Self::CompleteRequest(client_request_params(method, params)?),
+            _ => Self::CustomRequest(CustomRequest {
+                method: method.to_string(),
+                params: client_request_params(method, params)?,
+            }),
+        };
+        Ok(request)
+    }
+
+    /// Returns `true` if this is a `tools/call` request for the tool named `name`.
+    pub fn is_for_tool(&self, name: &str) -> bool {
+        matches!(self, RequestFromClient::CallToolRequest(params) if params.name == name)
+    }
     /// Returns `true` if the request is an `InitializeRequest`.
     pub fn is_initialize_request(&self) -> bool {
         matches!(self, RequestFromClient::InitializeRequest(_))
     }
+
+    /// Returns the `_meta` object of the request params, if any.
+    pub fn meta(&self) -> Option<serde_json::Map<String, Value>> {
+        match self {
+            RequestFromClient::InitializeRequest(params) => request_meta_entries(&params.meta),
+            RequestFromClient::PingRequest(params) => request_meta_entries(&params.as_ref()?.meta),
+            RequestFromClient::ListResourcesRequest(params) => request_meta_entries(&params.as_ref()?.meta),
+            RequestFromClient::ListResourceTemplatesRequest(params) => request_meta_entries(&params.as_ref()?.meta),
+            RequestFromClient::ReadResourceRequest(params) => request_meta_entries(&params.meta),
+            RequestFromClient::SubscribeRequest(params) => request_meta_entries(&params.meta),
+            RequestFromClient::UnsubscribeRequest(params) => request_meta_entries(&params.meta),
+            RequestFromClient::ListPromptsRequest(params) => request_meta_entries(&params.as_ref()?.meta),
+            RequestFromClient::GetPromptRequest(params) => request_meta_entries(&params.meta),
+            RequestFromClient::ListToolsRequest(params) => request_meta_entries(&params.as_ref()?.meta),
+            RequestFromClient::CallToolRequest(params) => request_meta_entries(&params.meta),
+            RequestFromClient::ListTasksRequest(params) => request_meta_entries(&params.as_ref()?.meta),
+            RequestFromClient::SetLevelRequest(params) => request_meta_entries(&params.meta),
+            RequestFromClient::CompleteRequest(params) => request_meta_entries(&params.meta),
+            RequestFromClient::GetTaskRequest(_)
+            | RequestFromClient::GetTaskPayloadRequest(_)
+            | RequestFromClient::CancelTaskRequest(_) => None,
+            RequestFromClient::CustomRequest(request) => match request.params.as_ref()?.get("_meta") {
+                Some(Value::Object(meta)) => Some(meta.clone()),
+                _ => None,
+            },
+        }
+    }
+
+    /// Adds the given entries to the request params `_meta`, overwriting existing keys.
+    ///
+    /// Requests whose params do not define a `_meta` field are left unchanged.
+    pub fn extend_meta(&mut self, entries: &serde_json::Map<String, Value>) {
+        match self {
+            RequestFromClient::InitializeRequest(params) => extend_request_meta(&mut params.meta, entries),
+            RequestFromClient::PingRequest(params) => {
+                extend_request_meta(&mut params.get_or_insert_with(Default::default).meta, entries)
+            }
+            RequestFromClient::ListResourcesRequest(params) => {
+                extend_request_meta(&mut params.get_or_insert_with(Default::default).meta, entries)
+            }
+            RequestFromClient::ListResourceTemplatesRequest(params) => {
+                extend_request_meta(&mut params.get_or_insert_with(Default::default).meta, entries)
+            }
+            RequestFromClient::ReadResourceRequest(params) => extend_request_meta(&mut params.meta, entries),
+            RequestFromClient::SubscribeRequest(params) => extend_request_meta(&mut params.meta, entries),
+            RequestFromClient::UnsubscribeRequest(params) => extend_request_meta(&mut params.meta, entries),
+            RequestFromClient::ListPromptsRequest(params) => {
+                extend_request_meta(&mut params.get_or_insert_with(Default::default).meta, entries)
+            }
+            RequestFromClient::GetPromptRequest(params) => extend_request_meta(&mut params.meta, entries),
+            RequestFromClient::ListToolsRequest(params) => {
+                extend_request_meta(&mut params.get_or_insert_with(Default::default).meta, entries)
+            }
+            RequestFromClient::CallToolRequest(params) => extend_request_meta(&mut params.meta, entries),
+            RequestFromClient::ListTasksRequest(params) => {
+                extend_request_meta(&mut params.get_or_insert_with(Default::default).meta, entries)
+            }
+            RequestFromClient::SetLevelRequest(params) => extend_request_meta(&mut params.meta, entries),
+            RequestFromClient::CompleteRequest(params) => extend_request_meta(&mut params.meta, entries),
+            RequestFromClient::GetTaskRequest(_)
+            | RequestFromClient::GetTaskPayloadRequest(_)
+            | RequestFromClient::CancelTaskRequest(_) => {}
+            RequestFromClient::CustomRequest(request) => {
+                extend_meta_entries(request.params.get_or_insert_with(serde_json::Map::new), entries)
+            }
+        }
+    }
+
+    /// Returns the `_meta.progressToken` attached to the request params, if any.
+    pub fn progress_token(&self) -> Option<ProgressToken> {
+        progress_token_from_value(self.meta()?.get("progressToken")?)
+    }
+
+    /// Attaches a progress token to the request params `_meta`, requesting out-of-band
+    /// progress notifications for this request.
+    ///
+    /// Requests whose params do not define a `_meta` field are returned unchanged.
+    pub fn with_progress_token(mut self, token: ProgressToken) -> Self {
+        let entries = serde_json::Map::from_iter([("progressToken".to_string(), progress_token_value(&token))]);
+        self.extend_meta(&entries);
+        self
+    }
+}
+
+/// Deserializes the params of a `method` request, mapping failures to an `invalid_params` error.
+fn client_request_params<T: serde::de::DeserializeOwned>(method: &str, params: Value) -> std::result::Result<T, RpcError> {
+    serde_json::from_value(params)
+        .map_err(|error| RpcError::invalid_params().with_message(format!("Invalid params for '{method}': {error}")))
+}
+
+/// Adds the given entries to the `_meta` object of a params map,
+/// creating the `_meta` object if it does not exist yet.
+fn extend_meta_entries(params: &mut serde_json::Map<String, Value>, entries: &serde_json::Map<String, Value>) {
+    let meta = params.entry("_meta").or_insert_with(|| Value::Object(serde_json::Map::new()));
+    if let Some(meta) = meta.as_object_mut() {
+        meta.extend(entries.clone());
+    }
+}
+
+/// Typed `_meta` of standard request params.
+trait RequestMeta {
+    /// Returns the `_meta` entries as a JSON object.
+    fn entries(&self) -> serde_json::Map<String, Value>;
+    /// Adds the given entries, overwriting existing keys.
+    fn extend(&mut self, entries: &serde_json::Map<String, Value>);
+}
+
+/// Every request params type declares its own `_meta` struct, all made of
+/// a typed `progressToken` with the remaining entries flattened into `extra`.
+macro_rules! impl_request_meta {
+    ($($meta:ty),* $(,)?) => {
+        $(
+            impl RequestMeta for $meta {
+                fn entries(&self) -> serde_json::Map<String, Value> {
+                    let mut entries = self.extra.clone().unwrap_or_default();
+                    if let Some(token) = &self.progress_token {
+                        entries.insert("progressToken".to_string(), progress_token_value(token));
+                    }
+                    entries
+                }
+
+                fn extend(&mut self, entries: &serde_json::Map<String, Value>) {
+                    for (key, value) in entries {
+                        if key == "progressToken" {
+                            if let Some(token) = progress_token_from_value(value) {
+                                self.progress_token = Some(token);
+                            }
+                        } else {
+                            self.extra.get_or_insert_with(serde_json::Map::new).insert(key.clone(), value.clone());
+                        }
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_request_meta!(
+    CallToolMeta,
+    CompleteRequestMeta,
+    GetPromptMeta,
+    InitializeMeta,
+    PaginatedMeta,
+    ReadResourceMeta,
+    RequestParamsMeta,
+    SetLevelMeta,
+    SubscribeMeta,
+    UnsubscribeMeta,
+);
+
+/// Returns the entries of an optional typed request `_meta`.
+fn request_meta_entries<M: RequestMeta>(meta: &Option<M>) -> Option<serde_json::Map<String, Value>> {
+    meta.as_ref().map(RequestMeta::entries)
+}
+
+/// Adds the given entries to an optional typed request `_meta`, creating it if needed.
+fn extend_request_meta<M: RequestMeta + Default>(meta: &mut Option<M>, entries: &serde_json::Map<String, Value>) {
+    meta.get_or_insert_with(Default::default).extend(entries);
+}
+
+fn progress_token_value(token: &ProgressToken) -> Value {
+    match token {
+        ProgressToken::String(token) => Value::String(token.clone()),
+        ProgressToken::Integer(token) => Value::from(*token),
+    }
+}
+
+/// Parses a `progressToken` value, which must be a string or an integer.
+fn progress_token_from_value(value: &Value) -> Option<ProgressToken> {
+    match value {
+        Value::String(token) => Some(ProgressToken::String(token.clone())),
+        Value::Number(token) => token.as_i64().map(ProgressToken::Integer),
+        _ => None,
+    }
 }
 
 // impl From<ClientRequest> for RequestFromClient {
@@ -662,7 +1056,7 @@ impl RequestFromClient {
 //*******************************//
 
 /// "Similar to JsonrpcNotification , but with the variants restricted to client-side notifications."
-#[derive(Clone, Debug, ::serde::Deserialize, ::serde::Serialize)]
+#[derive(Clone, Debug, ::serde::Serialize)]
 #[serde(untagged)]
 pub enum ClientJsonrpcNotification {
     CancelledNotification(CancelledNotification),
@@ -673,6 +1067,54 @@ pub enum ClientJsonrpcNotification {
     CustomNotification(JsonrpcNotification),
 }
 
+/// Untagged representation `ClientJsonrpcNotification` deserializes through, once the payload is known to carry no `id`.
+#[allow(clippy::enum_variant_names)]
+#[derive(::serde::Deserialize)]
+#[serde(untagged, rename = "ClientJsonrpcNotification")]
+enum ClientJsonrpcNotificationRepr {
+    CancelledNotification(CancelledNotification),
+    InitializedNotification(InitializedNotification),
+    ProgressNotification(ProgressNotification),
+    TaskStatusNotification(TaskStatusNotification),
+    RootsListChangedNotification(RootsListChangedNotification),
+    CustomNotification(JsonrpcNotification),
+}
+
+impl<'de> ::serde::Deserialize<'de> for ClientJsonrpcNotification {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: ::serde::Deserializer<'de>,
+    {
+        let value = <Value as ::serde::Deserialize>::deserialize(deserializer)?;
+        // per JSON-RPC, a notification must not carry an id
+        if value.get("id").is_some() {
+            return Err(::serde::de::Error::custom("notifications must not carry an id"));
+        }
+        let notification = <ClientJsonrpcNotificationRepr as ::serde::Deserialize>::deserialize(value)
+            .map_err(::serde::de::Error::custom)?;
+        Ok(match notification {
+            ClientJsonrpcNotificationRepr::CancelledNotification(notification) => {
+                ClientJsonrpcNotification::CancelledNotification(notification)
+            }
+            ClientJsonrpcNotificationRepr::InitializedNotification(notification) => {
+                ClientJsonrpcNotification::InitializedNotification(notification)
+            }
+            ClientJsonrpcNotificationRepr::ProgressNotification(notification) => {
+                ClientJsonrpcNotification::ProgressNotification(notification)
+            }
+            ClientJsonrpcNotificationRepr::TaskStatusNotification(notification) => {
+                ClientJsonrpcNotification::TaskStatusNotification(notification)
+            }
+            ClientJsonrpcNotificationRepr::RootsListChangedNotification(notification) => {
+                ClientJsonrpcNotification::RootsListChangedNotification(notification)
+            }
+            ClientJsonrpcNotificationRepr::CustomNotification(notification) => {
+                ClientJsonrpcNotification::CustomNotification(notification)
+            }
+        })
+    }
+}
+
 impl ClientJsonrpcNotification {
     pub fn new(notification: NotificationFromClient) -> Self {
         match notification {
@@ -763,7 +1205,13 @@ impl FromStr for ClientJsonrpcNotification {
     type Err = RpcError;
 
     fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
-        serde_json::from_str(s)
+        let value: Value = serde_json::from_str(s)
+            .map_err(|error| RpcError::parse_error().with_data(Some(json!({ "details" : error.to_string() }))))?;
+        // per JSON-RPC, a notification must not carry an id
+        if value.get("id").is_some() {
+            return Err(RpcError::invalid_request().with_message("notifications must not carry an id".to_string()));
+        }
+        serde_json::from_value(value)
             .map_err(|error| RpcError::parse_error().with_data(Some(json!({ "details" : error.to_string() }))))
     }
 }
@@ -815,6 +1263,19 @@ impl NotificationFromClient {
             NotificationFromClient::CustomNotification(notification) => notification.method.as_str(),
         }
     }
+
+    /// Returns `true` if the notification method equals `method`, regardless of its params.
+    pub fn matches_method(&self, method: &str) -> bool {
+        self.method() == method
+    }
+
+    /// Dispatches to `on_standard` for notifications defined by the schema, or to `on_custom` for a `CustomNotification`.
+    pub fn visit<R>(&self, on_standard: impl FnOnce(&Self) -> R, on_custom: impl FnOnce(&CustomNotification) -> R) -> R {
+        match self {
+            NotificationFromClient::CustomNotification(notification) => on_custom(notification),
+            _ => on_standard(self),
+        }
+    }
 }
 
 //*******************************//
@@ -840,6 +1301,15 @@ impl ClientJsonrpcResponse {
     pub fn jsonrpc(&self) -> &::std::string::String {
         &self.jsonrpc
     }
+
+    /// Returns the id of the request this response belongs to.
+    pub fn id(&self) -> &RequestId {
+        &self.id
+    }
+    /// Returns an owned copy of the response id, convenient for moving into async tasks.
+    pub fn clone_id(&self) -> RequestId {
+        self.id.clone()
+    }
 }
 
 /// Formats the ClientJsonrpcResponse as a JSON string.
@@ -922,6 +1392,360 @@ impl FromStr for ClientMessage {
     }
 }
 
+impl ClientMessage {
+    /// Parses a `ClientMessage` like [`FromStr`], additionally reporting how each variant fared, to
+    /// help debug payloads that deserialize into an unexpected variant.
+    ///
+    /// Variants are tried in the same order as the untagged deserializer and the first that succeeds is
+    /// returned. The diagnostics hold one line per variant, e.g. `Request: matched (selected)` or
+    /// `Response: missing field `result``; several `matched` lines reveal an ambiguous payload.
+    pub fn parse_with_diagnostics(json: &str) -> (std::result::Result<ClientMessage, RpcError>, Vec<String>) {
+        let value: Value = match serde_json::from_str(json) {
+            Ok(value) => value,
+            Err(error) => {
+                let diagnostics = vec![format!("json: {error}")];
+                let error = RpcError::parse_error().with_data(Some(json!({ "details" : error.to_string() })));
+                return (Err(error), diagnostics);
+            }
+        };
+        let attempts = [
+            ("Request", serde_json::from_value::<ClientJsonrpcRequest>(value.clone()).err()),
+            (
+                "Notification",
+                serde_json::from_value::<ClientJsonrpcNotification>(value.clone()).err(),
+            ),
+            (
+                "Response",
+                serde_json::from_value::<ClientJsonrpcResponse>(value.clone()).err(),
+            ),
+            ("Error", serde_json::from_value::<JsonrpcErrorResponse>(value.clone()).err()),
+        ];
+        let mut selected = false;
+        let diagnostics = attempts
+            .into_iter()
+            .map(|(variant, error)| match error {
+                Some(error) => format!("{variant}: {error}"),
+                None if !selected => {
+                    selected = true;
+                    format!("{variant}: matched (selected)")
+                }
+                None => format!("{variant}: matched"),
+            })
+            .collect();
+        (ClientMessage::try_from(value), diagnostics)
+    }
+}
+
+/// Object keys whose string values [`ClientMessage::anonymize`] and [`ServerMessage::anonymize`] keep as is:
+/// protocol keywords and enum values that carry no user data but are needed to parse the message, and `name`,
+/// which holds the tool, prompt and capability identifiers needed to route and reproduce a message.
+/// A `name` member inside user supplied objects, such as tool arguments, is kept as well.
+pub const ANONYMIZE_KEPT_KEYS: &[&str] = &[
+    "type",
+    "method",
+    "name",
+    "jsonrpc",
+    "protocolVersion",
+    "mimeType",
+    "role",
+    "audience",
+    "level",
+    "action",
+    "status",
+    "format",
+    "includeContext",
+    "mode",
+    "taskSupport",
+    "theme",
+    "progressToken",
+    "requestId",
+    "taskId",
+];
+
+/// Replaces every string in `value` with a same-length placeholder, skipping [`ANONYMIZE_KEPT_KEYS`].
+fn anonymize_json(value: &mut Value) {
+    match value {
+        Value::String(text) => *text = "x".repeat(text.chars().count()),
+        Value::Array(items) => items.iter_mut().for_each(anonymize_json),
+        Value::Object(map) => map
+            .iter_mut()
+            .filter(|(key, _)| !ANONYMIZE_KEPT_KEYS.contains(&key.as_str()))
+            .for_each(|(_, member)| anonymize_json(member)),
+        Value::Null | Value::Bool(_) | Value::Number(_) => {}
+    }
+}
+
+/// Returns the path of the first non-null member of `raw` that is missing from `typed`,
+/// the same message after a round-trip through its typed representation.
+fn find_unknown_key(raw: &Value, typed: &Value, path: &str) -> Option<String> {
+    match (raw, typed) {
+        (Value::Object(raw), Value::Object(typed)) => {
+            raw.iter().filter(|(_, value)| !value.is_null()).find_map(|(key, value)| {
+                let path = if path.is_empty() {
+                    key.to_string()
+                } else {
+                    format!("{path}.{key}")
+                };
+                match typed.get(key) {
+                    Some(typed) => find_unknown_key(value, typed, &path),
+                    None => Some(path),
+                }
+            })
+        }
+        (Value::Array(raw), Value::Array(typed)) => raw
+            .iter()
+            .zip(typed)
+            .enumerate()
+            .find_map(|(index, (raw, typed))| find_unknown_key(raw, typed, &format!("{path}[{index}]"))),
+        _ => None,
+    }
+}
+
+/// Parses a `ClientMessage`, rejecting standard requests and notifications that carry properties
+/// this MCP schema version does not define.
+///
+/// Intended for connections that have already negotiated this schema version, to catch peers speaking another one.
+/// The known properties are the ones the typed message keeps when it is serialized back, so free-form members such
+/// as tool `arguments` or `_meta` are accepted as is.
+/// Custom requests and notifications, responses and errors are not checked.
+///
+/// # Errors
+/// Returns an `invalid_request` `RpcError` naming the offending property,
+/// or a `parse_error` if the input is not a valid message.
+pub fn parse_client_message_strict_version(json: &str) -> std::result::Result<ClientMessage, RpcError> {
+    let value: Value = serde_json::from_str(json)
+        .map_err(|error| RpcError::parse_error().with_data(Some(json!({ "details" : error.to_string() }))))?;
+    let message = ClientMessage::try_from(value.clone())?;
+    let standard = match &message {
+        ClientMessage::Request(request) => !matches!(request, ClientJsonrpcRequest::CustomRequest(_)),
+        ClientMessage::Notification(notification) => {
+            !matches!(notification, ClientJsonrpcNotification::CustomNotification(_))
+        }
+        ClientMessage::Response(_) | ClientMessage::Error(_) => false,
+    };
+    if standard {
+        if let Some(path) = find_unknown_key(&value, &message.to_value()?, "") {
+            return Err(RpcError::invalid_request()
+                .with_message(format!("Property `{path}` is not defined by this protocol version")));
+        }
+    }
+    Ok(message)
+}
+
+/// Parses a `ClientMessage`, rejecting input whose arrays and objects nest deeper than `max_depth`.
+///
+/// The nesting is measured on the raw text before any deserialization takes place, so a hostile
+/// peer cannot exhaust the stack with deeply nested `params`.
+///
+/// # Errors
+/// Returns an `invalid_request` `RpcError` if the input is too deep, or a `parse_error` if it is not a valid message.
+pub fn parse_client_message_with_limits(json: &str, max_depth: usize) -> std::result::Result<ClientMessage, RpcError> {
+    if exceeds_json_depth(json, max_depth) {
+        return Err(
+            RpcError::invalid_request().with_message(format!("JSON nesting exceeds the maximum depth of {max_depth}"))
+        );
+    }
+    ClientMessage::from_str(json)
+}
+
+/// Largest frame body, in bytes, that `decode_framed` accepts from a `Content-Length` header.
+pub const MAX_FRAME_SIZE: usize = 16 * 1024 * 1024;
+
+/// Longest frame header line, in bytes, that `decode_framed` reads before rejecting the header.
+pub const MAX_HEADER_LINE: usize = 8 * 1024;
+
+/// Frames a message with an LSP-style `Content-Length` header, as used by some stream transports.
+pub fn encode_framed(msg: &impl Display) -> String {
+    let body = msg.to_string();
+    format!("Content-Length: {}\r\n\r\n{body}", body.len())
+}
+
+/// Reads one `Content-Length` framed `ServerMessage` from `reader`.
+///
+/// Header names are matched case-insensitively and headers other than `Content-Length` are ignored.
+/// Returns `Ok(None)` if the stream ends cleanly before a new frame starts.
+///
+/// # Errors
+/// Returns a `parse_error` `RpcError` if the headers are malformed or missing `Content-Length`,
+/// if a header line exceeds [`MAX_HEADER_LINE`], if `Content-Length` exceeds [`MAX_FRAME_SIZE`],
+/// if the stream ends before the full body is read, or if the body is not a valid message.
+pub fn decode_framed<R: std::io::BufRead>(reader: &mut R) -> std::result::Result<Option<ServerMessage>, RpcError> {
+    use std::io::{BufRead, Read};
+
+    let io_error = |error: std::io::Error| RpcError::parse_error().with_data(Some(json!({ "details" : error.to_string() })));
+
+    let mut content_length = None;
+    let mut started = false;
+    loop {
+        let mut line = String::new();
+        let read = reader
+            .by_ref()
+            .take(MAX_HEADER_LINE as u64)
+            .read_line(&mut line)
+            .map_err(io_error)?;
+        if read == 0 {
+            if started {
+                return Err(RpcError::parse_error().with_message("Stream ended inside the frame headers".to_string()));
+            }
+            return Ok(None);
+        }
+        started = true;
+        if read == MAX_HEADER_LINE && !line.ends_with('\n') {
+            return Err(RpcError::parse_error()
+                .with_message(format!("Malformed frame header, line exceeds {MAX_HEADER_LINE} bytes")));
+        }
+        let line = line.trim_end_matches(['\r', '\n']);
+        if line.is_empty() {
+            break;
+        }
+        let (name, value) = line
+            .split_once(':')
+            .ok_or_else(|| RpcError::parse_error().with_message(format!("Malformed frame header '{line}'")))?;
+        if name.trim().eq_ignore_ascii_case("Content-Length") {
+            let length = value
+                .trim()
+                .parse::<usize>()
+                .map_err(|_| RpcError::parse_error().with_message(format!("Invalid Content-Length '{}'", value.trim())))?;
+            if length > MAX_FRAME_SIZE {
+                return Err(RpcError::parse_error().with_message(format!(
+                    "Content-Length {length} exceeds the maximum frame size of {MAX_FRAME_SIZE} bytes"
+                )));
+            }
+            content_length = Some(length);
+        }
+    }
+
+    let content_length =
+        content_length.ok_or_else(|| RpcError::parse_error().with_message("Missing Content-Length header".to_string()))?;
+    let mut body = Vec::new();
+    reader.take(content_length as u64).read_to_end(&mut body).map_err(io_error)?;
+    if body.len() < content_length {
+        return Err(RpcError::parse_error().with_message(format!("Truncated frame body, expected {content_length} bytes")));
+    }
+    let body = String::from_utf8(body)
+        .map_err(|error| RpcError::parse_error().with_data(Some(json!({ "details" : error.to_string() }))))?;
+    ServerMessage::from_str(&body).map(Some)
+}
+
+/// Reads only the top-level `method` of a raw JSON-RPC message, for routing before full deserialization.
+///
+/// All other members, including `params`, are skipped without being materialized.
+/// Returns `None` if the input is not a JSON object or has no string `method`, as is the case for responses and errors.
+pub fn peek_method(json: &str) -> Option<String> {
+    #[derive(::serde::Deserialize)]
+    struct MethodPeek {
+        method: Option<String>,
+    }
+    serde_json::from_str::<MethodPeek>(json).ok()?.method
+}
+
+/// Parses a `ServerMessage`, rejecting responses whose raw `result` is larger than `max_result_bytes`.
+///
+/// The size of the `result` value is measured on the raw text before any deserialization takes
+/// place, so an oversized result is never materialized into typed structures.
+///
+/// # Errors
+/// Returns an `invalid_request` `RpcError` if the result is too large,
+/// or a `parse_error` if the input is not a valid message.
+pub fn parse_server_message_with_limits(
+    json: &str,
+    max_result_bytes: usize,
+) -> std::result::Result<ServerMessage, RpcError> {
+    if json.len() > max_result_bytes {
+        if let Some(result_len) = top_level_value_len(json, "result") {
+            if result_len > max_result_bytes {
+                return Err(RpcError::invalid_request().with_message(format!(
+                    "Result of {result_len} bytes exceeds the maximum of {max_result_bytes} bytes"
+                )));
+            }
+        }
+    }
+    ServerMessage::from_str(json)
+}
+
+/// Returns the length in bytes of the raw value stored under `key` in the top-level JSON object,
+/// or `None` if the key is not present. Surrounding whitespace is included in the length.
+fn top_level_value_len(json: &str, key: &str) -> Option<usize> {
+    let bytes = json.as_bytes();
+    let mut depth = 0usize;
+    let mut in_string = false;
+    let mut escaped = false;
+    let mut string_start = 0;
+    let mut last_key: Option<&str> = None;
+    let mut value_start: Option<usize> = None;
+    for (index, byte) in bytes.iter().enumerate() {
+        if in_string {
+            match byte {
+                _ if escaped => escaped = false,
+                b'\\' => escaped = true,
+                b'"' => {
+                    in_string = false;
+                    if depth == 1 && value_start.is_none() {
+                        last_key = json.get(string_start..index);
+                    }
+                }
+                _ => {}
+            }
+            continue;
+        }
+        match byte {
+            b'"' => {
+                in_string = true;
+                string_start = index + 1;
+            }
+            b'{' | b'[' => depth += 1,
+            b':' if depth == 1 && value_start.is_none() && last_key == Some(key) => value_start = Some(index + 1),
+            b',' if depth == 1 => {
+                if let Some(start) = value_start {
+                    return Some(index - start);
+                }
+                last_key = None;
+            }
+            b'}' | b']' => {
+                if depth == 1 {
+                    if let Some(start) = value_start {
+                        return Some(index - start);
+                    }
+                }
+                depth = depth.saturating_sub(1);
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Returns `true` as soon as the nesting of arrays and objects in `json` goes beyond `max_depth`.
+/// Brackets inside string literals are ignored.
+fn exceeds_json_depth(json: &str, max_depth: usize) -> bool {
+    let mut depth = 0usize;
+    let mut in_string = false;
+    let mut escaped = false;
+    for byte in json.bytes() {
+        if in_string {
+            match byte {
+                _ if escaped => escaped = false,
+                b'\\' => escaped = true,
+                b'"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+        match byte {
+            b'"' => in_string = true,
+            b'{' | b'[' => {
+                depth += 1;
+                if depth > max_depth {
+                    return true;
+                }
+            }
+            b'}' | b']' => depth = depth.saturating_sub(1),
+            _ => {}
+        }
+    }
+    false
+}
+
 impl Display for ClientMessage {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
@@ -932,33 +1756,218 @@ impl Display for ClientMessage {
     }
 }
 
-//*******************//
-//** ServerMessage **//
-//*******************//
+impl ClientMessage {
+    /// Converts the message into a `serde_json::Value`.
+    ///
+    /// # Errors
+    /// Returns an `internal_error` `RpcError` if the message cannot be serialized.
+    pub fn to_value(&self) -> std::result::Result<Value, RpcError> {
+        serde_json::to_value(self)
+            .map_err(|error| RpcError::internal_error().with_data(Some(json!({ "details" : error.to_string() }))))
+    }
 
-/// "Similar to JsonrpcMessage, but with the variants restricted to client-side messages."
-/// ServerMessage represents a message sent by an MCP Server and received by an MCP Client.
-#[derive(::serde::Deserialize, ::serde::Serialize, Clone, Debug)]
-#[serde(untagged)]
-pub enum ServerMessage {
-    Request(ServerJsonrpcRequest),
-    Notification(ServerJsonrpcNotification),
-    Response(ServerJsonrpcResponse),
-    Error(JsonrpcErrorResponse),
-}
+    /// Serializes the message as compact JSON directly into `writer`, without an intermediate `String`.
+    ///
+    /// # Errors
+    /// Returns an `internal_error` `RpcError` if the message cannot be serialized or written.
+    pub fn write_to<W: std::io::Write>(&self, writer: W) -> std::result::Result<(), RpcError> {
+        serde_json::to_writer(writer, self)
+            .map_err(|error| RpcError::internal_error().with_data(Some(json!({ "details" : error.to_string() }))))
+    }
 
-impl ServerMessage {
-    /// Converts the current message into a `ServerJsonrpcResponse` if it's of the correct type.
+    /// Like [`Self::write_to`], followed by a trailing newline, as expected by newline-delimited transports such as stdio.
     ///
-    /// This function checks if the current message is of type `Response`. If so, it returns the
-    /// `ServerJsonrpcResponse` wrapped in a `Result::Ok`. If the message is not a `Response`,
-    /// it returns an error with a descriptive message indicating the mismatch in expected message types.
+    /// # Errors
+    /// Returns an `internal_error` `RpcError` if the message cannot be serialized or written.
+    pub fn write_framed_to<W: std::io::Write>(&self, mut writer: W) -> std::result::Result<(), RpcError> {
+        self.write_to(&mut writer)?;
+        writer
+            .write_all(b"\n")
+            .map_err(|error| RpcError::internal_error().with_data(Some(json!({ "details" : error.to_string() }))))
+    }
+
+    /// Builds the `notifications/initialized` message a client sends once initialization has completed.
+    pub fn initialized() -> ClientMessage {
+        ClientMessage::Notification(ClientJsonrpcNotification::new(
+            NotificationFromClient::InitializedNotification(None),
+        ))
+    }
+
+    /// Builds a message notifying the server that the list of roots has changed.
     ///
-    /// # Returns
-    /// - `Ok(ServerJsonrpcResponse)` if the message is a valid `Response`.
-    /// - `Err(RpcError)` if the message type is invalid
-    pub fn as_response(self) -> std::result::Result<ServerJsonrpcResponse, RpcError> {
-        if let Self::Response(response) = self {
+    /// Server-side counterparts are available as free functions, such as [`notify_tools_changed`].
+    pub fn roots_list_changed() -> ClientMessage {
+        ClientMessage::Notification(ClientJsonrpcNotification::new(
+            NotificationFromClient::RootsListChangedNotification(None),
+        ))
+    }
+
+    /// Formats the ClientMessage as a pretty-printed JSON string, for debugging.
+    /// `Display` stays compact for use on the wire.
+    pub fn to_pretty_string(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap_or_else(|err| format!("Serialization error: {err}"))
+    }
+
+    /// Wraps `request` with `id` into a `ClientMessage::Request`.
+    pub fn new_request(id: RequestId, request: impl Into<RequestFromClient>) -> Self {
+        ClientMessage::Request(ClientJsonrpcRequest::new(id, request.into()))
+    }
+
+    /// Wraps `notification` into a `ClientMessage::Notification`.
+    pub fn new_notification(notification: impl Into<NotificationFromClient>) -> Self {
+        ClientMessage::Notification(ClientJsonrpcNotification::new(notification.into()))
+    }
+
+    /// Wraps `result` with the id of the request it answers into a `ClientMessage::Response`.
+    pub fn new_response(id: RequestId, result: impl Into<ResultFromClient>) -> Self {
+        ClientMessage::Response(ClientJsonrpcResponse::new(id, result.into()))
+    }
+
+    /// Replaces the id of a request, response or error, e.g. when a proxy re-keys messages
+    /// between upstreams. Notifications carry no id and are returned unchanged.
+    pub fn with_request_id(mut self, id: RequestId) -> Self {
+        match &mut self {
+            ClientMessage::Request(request) => *request.request_id_mut() = id,
+            ClientMessage::Response(response) => response.id = id,
+            ClientMessage::Error(error) => error.id = Some(id),
+            ClientMessage::Notification(_) => {}
+        }
+        self
+    }
+
+    /// Returns a stable label for metrics and tracing spans: `request:<method>`, `notification:<method>`,
+    /// `response` or `error`.
+    pub fn span_name(&self) -> String {
+        match self {
+            ClientMessage::Request(request) => format!("request:{}", request.method()),
+            ClientMessage::Notification(notification) => format!("notification:{}", notification.method()),
+            ClientMessage::Response(_) => "response".to_string(),
+            ClientMessage::Error(_) => "error".to_string(),
+        }
+    }
+
+    /// Returns a copy with every string inside `params`, `result` and `error` replaced by a placeholder of
+    /// the same length (e.g. `"secret"` becomes `"xxxxxx"`), so captured traffic can be shared in bug reports.
+    ///
+    /// Method names, ids, numbers (including error codes), booleans and the shape of objects and arrays are
+    /// kept, as are protocol keywords such as `type`, `role` or `level` (see [`ANONYMIZE_KEPT_KEYS`]) so the
+    /// copy parses into the same variant.
+    ///
+    /// # Errors
+    /// Returns an error if the anonymized message no longer parses, for instance when a scrubbed string was
+    /// constrained by the schema. Falling back to the original message instead would leak the data this
+    /// method is meant to remove, and returning a different message would hide the failure.
+    pub fn anonymize(&self) -> std::result::Result<Self, RpcError> {
+        let mut value = serde_json::to_value(self)?;
+        if let Value::Object(map) = &mut value {
+            for key in ["params", "result", "error"] {
+                if let Some(member) = map.get_mut(key) {
+                    anonymize_json(member);
+                }
+            }
+        }
+        ClientMessage::try_from(value)
+    }
+
+    /// Compares the id, method and payload of two messages, ignoring the `jsonrpc` version member.
+    /// Intended for assertions in tests, where the version is not of interest.
+    pub fn semantically_eq(&self, other: &Self) -> bool {
+        without_jsonrpc(self) == without_jsonrpc(other)
+    }
+
+    /// Splits the message into its optional id and the transport-independent payload.
+    ///
+    /// This is the inverse of [`FromMessage::from_message`]; notifications have no id.
+    pub fn into_parts(self) -> (Option<RequestId>, MessageFromClient) {
+        match self {
+            ClientMessage::Request(request) => (
+                Some(request.request_id().clone()),
+                MessageFromClient::RequestFromClient(request.into()),
+            ),
+            ClientMessage::Notification(notification) => {
+                (None, MessageFromClient::NotificationFromClient(notification.into()))
+            }
+            ClientMessage::Response(response) => (Some(response.id), MessageFromClient::ResultFromClient(response.result)),
+            ClientMessage::Error(error) => (error.id, MessageFromClient::Error(error.error)),
+        }
+    }
+}
+
+impl TryFrom<Value> for ClientMessage {
+    type Error = RpcError;
+
+    fn try_from(value: Value) -> std::result::Result<Self, RpcError> {
+        serde_json::from_value(value)
+            .map_err(|error| RpcError::parse_error().with_data(Some(json!({ "details" : error.to_string() }))))
+    }
+}
+
+//*******************//
+//** ServerMessage **//
+//*******************//
+
+/// The outcome of a request as answered by the server, either a successful result or an error.
+#[derive(Clone, Debug)]
+pub enum ResponseOutcome {
+    Ok(ResultFromServer),
+    Err(RpcError),
+}
+
+/// "Similar to JsonrpcMessage, but with the variants restricted to client-side messages."
+/// ServerMessage represents a message sent by an MCP Server and received by an MCP Client.
+#[derive(::serde::Serialize, Clone, Debug)]
+#[serde(untagged)]
+pub enum ServerMessage {
+    Request(ServerJsonrpcRequest),
+    Notification(ServerJsonrpcNotification),
+    Response(ServerJsonrpcResponse),
+    Error(JsonrpcErrorResponse),
+}
+
+/// Untagged representation `ServerMessage` deserializes through, once the payload has been normalized.
+#[derive(::serde::Deserialize)]
+#[serde(untagged, rename = "ServerMessage")]
+enum ServerMessageRepr {
+    Request(ServerJsonrpcRequest),
+    Notification(ServerJsonrpcNotification),
+    Response(ServerJsonrpcResponse),
+    Error(JsonrpcErrorResponse),
+}
+
+impl<'de> ::serde::Deserialize<'de> for ServerMessage {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: ::serde::Deserializer<'de>,
+    {
+        let mut value = <Value as ::serde::Deserialize>::deserialize(deserializer)?;
+        drop_null_error(&mut value);
+        let message = <ServerMessageRepr as ::serde::Deserialize>::deserialize(value).map_err(::serde::de::Error::custom)?;
+        Ok(match message {
+            ServerMessageRepr::Request(message) => ServerMessage::Request(message),
+            ServerMessageRepr::Notification(message) => ServerMessage::Notification(message),
+            ServerMessageRepr::Response(message) => ServerMessage::Response(message),
+            ServerMessageRepr::Error(message) => ServerMessage::Error(message),
+        })
+    }
+}
+
+/// A reference-counted `ServerMessage`, cheap to clone when fanning a message out to many subscribers,
+/// e.g. through a broadcast channel. Create one with `SharedServerMessage::from(message)` or `message.into()`;
+/// it serializes exactly like the inner message.
+pub type SharedServerMessage = std::sync::Arc<ServerMessage>;
+
+impl ServerMessage {
+    /// Converts the current message into a `ServerJsonrpcResponse` if it's of the correct type.
+    ///
+    /// This function checks if the current message is of type `Response`. If so, it returns the
+    /// `ServerJsonrpcResponse` wrapped in a `Result::Ok`. If the message is not a `Response`,
+    /// it returns an error with a descriptive message indicating the mismatch in expected message types.
+    ///
+    /// # Returns
+    /// - `Ok(ServerJsonrpcResponse)` if the message is a valid `Response`.
+    /// - `Err(RpcError)` if the message type is invalid
+    pub fn as_response(self) -> std::result::Result<ServerJsonrpcResponse, RpcError> {
+        if let Self::Response(response) = self {
             Ok(response)
         } else {
             Err(RpcError::internal_error().with_message(format!(
@@ -1031,6 +2040,20 @@ impl ServerMessage {
             )))
         }
     }
+
+    /// Returns the flush priority of the message, lower values should be sent first.
+    ///
+    /// Errors and responses unblock requests the peer is already waiting on, so they come first,
+    /// followed by requests and finally notifications, which are informational and can tolerate delay.
+    /// The ordering is stable: `Error` = 0, `Response` = 1, `Request` = 2, `Notification` = 3.
+    pub fn priority(&self) -> u8 {
+        match self {
+            ServerMessage::Error(_) => 0,
+            ServerMessage::Response(_) => 1,
+            ServerMessage::Request(_) => 2,
+            ServerMessage::Notification(_) => 3,
+        }
+    }
 }
 
 impl From<ServerJsonrpcNotification> for ServerMessage {
@@ -1162,6 +2185,187 @@ impl Display for ServerMessage {
     }
 }
 
+impl ServerMessage {
+    /// Converts the message into a `serde_json::Value`.
+    ///
+    /// # Errors
+    /// Returns an `internal_error` `RpcError` if the message cannot be serialized.
+    pub fn to_value(&self) -> std::result::Result<Value, RpcError> {
+        serde_json::to_value(self)
+            .map_err(|error| RpcError::internal_error().with_data(Some(json!({ "details" : error.to_string() }))))
+    }
+
+    /// Serializes the message as compact JSON directly into `writer`, without an intermediate `String`.
+    ///
+    /// # Errors
+    /// Returns an `internal_error` `RpcError` if the message cannot be serialized or written.
+    pub fn write_to<W: std::io::Write>(&self, writer: W) -> std::result::Result<(), RpcError> {
+        serde_json::to_writer(writer, self)
+            .map_err(|error| RpcError::internal_error().with_data(Some(json!({ "details" : error.to_string() }))))
+    }
+
+    /// Like [`Self::write_to`], followed by a trailing newline, as expected by newline-delimited transports such as stdio.
+    ///
+    /// # Errors
+    /// Returns an `internal_error` `RpcError` if the message cannot be serialized or written.
+    pub fn write_framed_to<W: std::io::Write>(&self, mut writer: W) -> std::result::Result<(), RpcError> {
+        self.write_to(&mut writer)?;
+        writer
+            .write_all(b"\n")
+            .map_err(|error| RpcError::internal_error().with_data(Some(json!({ "details" : error.to_string() }))))
+    }
+
+    /// Formats the ServerMessage as a pretty-printed JSON string, for debugging.
+    /// `Display` stays compact for use on the wire.
+    pub fn to_pretty_string(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap_or_else(|err| format!("Serialization error: {err}"))
+    }
+
+    /// Wraps `request` with `id` into a `ServerMessage::Request`.
+    pub fn new_request(id: RequestId, request: impl Into<RequestFromServer>) -> Self {
+        ServerMessage::Request(ServerJsonrpcRequest::new(id, request.into()))
+    }
+
+    /// Wraps `notification` into a `ServerMessage::Notification`.
+    pub fn new_notification(notification: impl Into<NotificationFromServer>) -> Self {
+        ServerMessage::Notification(ServerJsonrpcNotification::new(notification.into()))
+    }
+
+    /// Wraps `result` with the id of the request it answers into a `ServerMessage::Response`.
+    pub fn new_response(id: RequestId, result: impl Into<ResultFromServer>) -> Self {
+        ServerMessage::Response(ServerJsonrpcResponse::new(id, result.into()))
+    }
+
+    /// Replaces the id of a request, response or error, e.g. when a proxy re-keys messages
+    /// between upstreams. Notifications carry no id and are returned unchanged.
+    pub fn with_request_id(mut self, id: RequestId) -> Self {
+        match &mut self {
+            ServerMessage::Request(request) => *request.request_id_mut() = id,
+            ServerMessage::Response(response) => response.id = id,
+            ServerMessage::Error(error) => error.id = Some(id),
+            ServerMessage::Notification(_) => {}
+        }
+        self
+    }
+
+    /// Returns a stable label for metrics and tracing spans: `request:<method>`, `notification:<method>`,
+    /// `response` or `error`.
+    pub fn span_name(&self) -> String {
+        match self {
+            ServerMessage::Request(request) => format!("request:{}", request.method()),
+            ServerMessage::Notification(notification) => format!("notification:{}", notification.method()),
+            ServerMessage::Response(_) => "response".to_string(),
+            ServerMessage::Error(_) => "error".to_string(),
+        }
+    }
+
+    /// Returns a copy with every string inside `params`, `result` and `error` replaced by a placeholder of
+    /// the same length (e.g. `"secret"` becomes `"xxxxxx"`), so captured traffic can be shared in bug reports.
+    ///
+    /// Method names, ids, numbers (including error codes), booleans and the shape of objects and arrays are
+    /// kept, as are protocol keywords such as `type`, `role` or `level` (see [`ANONYMIZE_KEPT_KEYS`]) so the
+    /// copy parses into the same variant.
+    ///
+    /// # Errors
+    /// Returns an error if the anonymized message no longer parses, for instance when a scrubbed string was
+    /// constrained by the schema. Falling back to the original message instead would leak the data this
+    /// method is meant to remove, and returning a different message would hide the failure.
+    pub fn anonymize(&self) -> std::result::Result<Self, RpcError> {
+        let mut value = serde_json::to_value(self)?;
+        if let Value::Object(map) = &mut value {
+            for key in ["params", "result", "error"] {
+                if let Some(member) = map.get_mut(key) {
+                    anonymize_json(member);
+                }
+            }
+        }
+        ServerMessage::try_from(value)
+    }
+
+    /// Compares the id, method and payload of two messages, ignoring the `jsonrpc` version member.
+    /// Intended for assertions in tests, where the version is not of interest.
+    pub fn semantically_eq(&self, other: &Self) -> bool {
+        without_jsonrpc(self) == without_jsonrpc(other)
+    }
+
+    /// Unifies `Response` and `Error` messages into the id of the request they answer and its outcome.
+    /// Returns `None` for requests, notifications and errors that carry no id.
+    pub fn as_outcome(self) -> Option<(RequestId, ResponseOutcome)> {
+        match self {
+            ServerMessage::Response(response) => Some((response.id, ResponseOutcome::Ok(response.result))),
+            ServerMessage::Error(error) => error.id.map(|id| (id, ResponseOutcome::Err(error.error))),
+            ServerMessage::Request(_) | ServerMessage::Notification(_) => None,
+        }
+    }
+
+    /// Splits the message into its optional id and the transport-independent payload.
+    ///
+    /// This is the inverse of [`FromMessage::from_message`]; notifications have no id.
+    pub fn into_parts(self) -> (Option<RequestId>, MessageFromServer) {
+        match self {
+            ServerMessage::Request(request) => (
+                Some(request.request_id().clone()),
+                MessageFromServer::RequestFromServer(request.into()),
+            ),
+            ServerMessage::Notification(notification) => {
+                (None, MessageFromServer::NotificationFromServer(notification.into()))
+            }
+            ServerMessage::Response(response) => (Some(response.id), MessageFromServer::ResultFromServer(response.result)),
+            ServerMessage::Error(error) => (error.id, MessageFromServer::Error(error.error)),
+        }
+    }
+
+    /// Returns `true` if this message is the response (or error) for `request`.
+    ///
+    /// The message id must equal the request id and, for successful responses, the result
+    /// variant must be one the request method can produce. Custom requests accept any result.
+    pub fn is_response_to(&self, request: &ClientJsonrpcRequest) -> bool {
+        match self {
+            ServerMessage::Response(response) => {
+                response.id() == request.request_id() && Self::result_matches(&response.result, request)
+            }
+            ServerMessage::Error(error) => error.id.as_ref() == Some(request.request_id()),
+            ServerMessage::Request(_) | ServerMessage::Notification(_) => false,
+        }
+    }
+
+    fn result_matches(result: &ResultFromServer, request: &ClientJsonrpcRequest) -> bool {
+        use ClientJsonrpcRequest as Req;
+        use ResultFromServer as Res;
+        match request {
+            Req::InitializeRequest(_) => matches!(result, Res::InitializeResult(_)),
+            Req::PingRequest(_) | Req::SubscribeRequest(_) | Req::UnsubscribeRequest(_) | Req::SetLevelRequest(_) => {
+                matches!(result, Res::Result(_))
+            }
+            Req::ListResourcesRequest(_) => matches!(result, Res::ListResourcesResult(_)),
+            Req::ListResourceTemplatesRequest(_) => matches!(result, Res::ListResourceTemplatesResult(_)),
+            Req::ReadResourceRequest(_) => matches!(result, Res::ReadResourceResult(_)),
+            Req::ListPromptsRequest(_) => matches!(result, Res::ListPromptsResult(_)),
+            Req::GetPromptRequest(_) => matches!(result, Res::GetPromptResult(_)),
+            Req::ListToolsRequest(_) => matches!(result, Res::ListToolsResult(_)),
+            // task-augmented tool calls are answered with a CreateTaskResult
+            Req::CallToolRequest(_) => matches!(result, Res::CallToolResult(_) | Res::CreateTaskResult(_)),
+            // GetTaskResult and CancelTaskResult share the same shape and are indistinguishable once deserialized
+            Req::GetTaskRequest(_) | Req::CancelTaskRequest(_) => {
+                matches!(result, Res::GetTaskResult(_) | Res::CancelTaskResult(_))
+            }
+            Req::ListTasksRequest(_) => matches!(result, Res::ListTasksResult(_)),
+            Req::CompleteRequest(_) => matches!(result, Res::CompleteResult(_)),
+            // the payload is the result of the underlying request, whatever its type
+            Req::GetTaskPayloadRequest(_) | Req::CustomRequest(_) => true,
+        }
+    }
+}
+
+impl TryFrom<Value> for ServerMessage {
+    type Error = RpcError;
+
+    fn try_from(value: Value) -> std::result::Result<Self, RpcError> {
+        serde_json::from_value(value)
+            .map_err(|error| RpcError::parse_error().with_data(Some(json!({ "details" : error.to_string() }))))
+    }
+}
+
 //**************************//
 //** ServerJsonrpcRequest **//
 //**************************//
@@ -1219,6 +2423,30 @@ impl ServerJsonrpcRequest {
         }
     }
 
+    fn request_id_mut(&mut self) -> &mut RequestId {
+        match self {
+            ServerJsonrpcRequest::PingRequest(request) => &mut request.id,
+            ServerJsonrpcRequest::GetTaskRequest(request) => &mut request.id,
+            ServerJsonrpcRequest::GetTaskPayloadRequest(request) => &mut request.id,
+            ServerJsonrpcRequest::CancelTaskRequest(request) => &mut request.id,
+            ServerJsonrpcRequest::ListTasksRequest(request) => &mut request.id,
+            ServerJsonrpcRequest::CreateMessageRequest(request) => &mut request.id,
+            ServerJsonrpcRequest::ListRootsRequest(request) => &mut request.id,
+            ServerJsonrpcRequest::ElicitRequest(request) => &mut request.id,
+            ServerJsonrpcRequest::CustomRequest(request) => &mut request.id,
+        }
+    }
+
+    /// Returns the id of the request, same as [`Self::request_id`].
+    pub fn id(&self) -> &RequestId {
+        self.request_id()
+    }
+
+    /// Returns an owned copy of the request id, convenient for moving into async tasks.
+    pub fn clone_id(&self) -> RequestId {
+        self.request_id().clone()
+    }
+
     pub fn jsonrpc(&self) -> &::std::string::String {
         match self {
             ServerJsonrpcRequest::PingRequest(request) => request.jsonrpc(),
@@ -1336,6 +2564,19 @@ impl RequestFromServer {
             RequestFromServer::CustomRequest(request) => request.method.as_str(),
         }
     }
+
+    /// Returns `true` if the request method equals `method`, regardless of its params.
+    pub fn matches_method(&self, method: &str) -> bool {
+        self.method() == method
+    }
+
+    /// Dispatches to `on_standard` for requests defined by the schema, or to `on_custom` for a `CustomRequest`.
+    pub fn visit<R>(&self, on_standard: impl FnOnce(&Self) -> R, on_custom: impl FnOnce(&CustomRequest) -> R) -> R {
+        match self {
+            RequestFromServer::CustomRequest(request) => on_custom(request),
+            _ => on_standard(self),
+        }
+    }
 }
 
 //*******************************//
@@ -1343,7 +2584,7 @@ impl RequestFromServer {
 //*******************************//
 
 /// "Similar to JsonrpcNotification , but with the variants restricted to server-side notifications."
-#[derive(Clone, Debug, ::serde::Deserialize, ::serde::Serialize)]
+#[derive(Clone, Debug, ::serde::Serialize)]
 #[serde(untagged)]
 pub enum ServerJsonrpcNotification {
     CancelledNotification(CancelledNotification),
@@ -1358,6 +2599,70 @@ pub enum ServerJsonrpcNotification {
     CustomNotification(JsonrpcNotification),
 }
 
+/// Untagged representation `ServerJsonrpcNotification` deserializes through, once the payload is known to carry no `id`.
+#[allow(clippy::enum_variant_names)]
+#[derive(::serde::Deserialize)]
+#[serde(untagged, rename = "ServerJsonrpcNotification")]
+enum ServerJsonrpcNotificationRepr {
+    CancelledNotification(CancelledNotification),
+    ProgressNotification(ProgressNotification),
+    ResourceListChangedNotification(ResourceListChangedNotification),
+    ResourceUpdatedNotification(ResourceUpdatedNotification),
+    PromptListChangedNotification(PromptListChangedNotification),
+    ToolListChangedNotification(ToolListChangedNotification),
+    TaskStatusNotification(TaskStatusNotification),
+    LoggingMessageNotification(LoggingMessageNotification),
+    ElicitationCompleteNotification(ElicitationCompleteNotification),
+    CustomNotification(JsonrpcNotification),
+}
+
+impl<'de> ::serde::Deserialize<'de> for ServerJsonrpcNotification {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: ::serde::Deserializer<'de>,
+    {
+        let value = <Value as ::serde::Deserialize>::deserialize(deserializer)?;
+        // per JSON-RPC, a notification must not carry an id
+        if value.get("id").is_some() {
+            return Err(::serde::de::Error::custom("notifications must not carry an id"));
+        }
+        let notification = <ServerJsonrpcNotificationRepr as ::serde::Deserialize>::deserialize(value)
+            .map_err(::serde::de::Error::custom)?;
+        Ok(match notification {
+            ServerJsonrpcNotificationRepr::CancelledNotification(notification) => {
+                ServerJsonrpcNotification::CancelledNotification(notification)
+            }
+            ServerJsonrpcNotificationRepr::ProgressNotification(notification) => {
+                ServerJsonrpcNotification::ProgressNotification(notification)
+            }
+            ServerJsonrpcNotificationRepr::ResourceListChangedNotification(notification) => {
+                ServerJsonrpcNotification::ResourceListChangedNotification(notification)
+            }
+            ServerJsonrpcNotificationRepr::ResourceUpdatedNotification(notification) => {
+                ServerJsonrpcNotification::ResourceUpdatedNotification(notification)
+            }
+            ServerJsonrpcNotificationRepr::PromptListChangedNotification(notification) => {
+                ServerJsonrpcNotification::PromptListChangedNotification(notification)
+            }
+            ServerJsonrpcNotificationRepr::ToolListChangedNotification(notification) => {
+                ServerJsonrpcNotification::ToolListChangedNotification(notification)
+            }
+            ServerJsonrpcNotificationRepr::TaskStatusNotification(notification) => {
+                ServerJsonrpcNotification::TaskStatusNotification(notification)
+            }
+            ServerJsonrpcNotificationRepr::LoggingMessageNotification(notification) => {
+                ServerJsonrpcNotification::LoggingMessageNotification(notification)
+            }
+            ServerJsonrpcNotificationRepr::ElicitationCompleteNotification(notification) => {
+                ServerJsonrpcNotification::ElicitationCompleteNotification(notification)
+            }
+            ServerJsonrpcNotificationRepr::CustomNotification(notification) => {
+                ServerJsonrpcNotification::CustomNotification(notification)
+            }
+        })
+    }
+}
+
 impl From<ServerJsonrpcNotification> for NotificationFromServer {
     fn from(notification: ServerJsonrpcNotification) -> Self {
         match notification {
@@ -1476,10 +2781,44 @@ impl FromStr for ServerJsonrpcNotification {
     type Err = RpcError;
 
     fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
-        serde_json::from_str(s)
+        let value: Value = serde_json::from_str(s)
+            .map_err(|error| RpcError::parse_error().with_data(Some(json!({ "details" : error.to_string() }))))?;
+        // per JSON-RPC, a notification must not carry an id
+        if value.get("id").is_some() {
+            return Err(RpcError::invalid_request().with_message("notifications must not carry an id".to_string()));
+        }
+        serde_json::from_value(value)
             .map_err(|error| RpcError::parse_error().with_data(Some(json!({ "details" : error.to_string() }))))
     }
 }
+
+/// Builds a `ServerMessage` notifying the client that the list of tools has changed.
+pub fn notify_tools_changed() -> ServerMessage {
+    ServerMessage::Notification(ServerJsonrpcNotification::new(
+        NotificationFromServer::ToolListChangedNotification(None),
+    ))
+}
+
+/// Builds a `ServerMessage` notifying the client that the list of resources has changed.
+pub fn notify_resources_changed() -> ServerMessage {
+    ServerMessage::Notification(ServerJsonrpcNotification::new(
+        NotificationFromServer::ResourceListChangedNotification(None),
+    ))
+}
+
+/// Builds a `ServerMessage` notifying the client that the list of prompts has changed.
+pub fn notify_prompts_changed() -> ServerMessage {
+    ServerMessage::Notification(ServerJsonrpcNotification::new(
+        NotificationFromServer::PromptListChangedNotification(None),
+    ))
+}
+
+/// Builds a `ServerMessage` notifying a subscribed client that the resource identified by `uri` has been updated.
+pub fn notify_resource_updated(uri: impl Into<String>) -> ServerMessage {
+    ServerMessage::Notification(ServerJsonrpcNotification::ResourceUpdatedNotification(
+        ResourceUpdatedNotification::for_uri(uri),
+    ))
+}
 //*******************************//
 //**  NotificationFromServer   **//
 //*******************************//
@@ -1520,6 +2859,19 @@ impl NotificationFromServer {
             NotificationFromServer::CustomNotification(params) => params.method.as_str(),
         }
     }
+
+    /// Returns `true` if the notification method equals `method`, regardless of its params.
+    pub fn matches_method(&self, method: &str) -> bool {
+        self.method() == method
+    }
+
+    /// Dispatches to `on_standard` for notifications defined by the schema, or to `on_custom` for a `CustomNotification`.
+    pub fn visit<R>(&self, on_standard: impl FnOnce(&Self) -> R, on_custom: impl FnOnce(&CustomNotification) -> R) -> R {
+        match self {
+            NotificationFromServer::CustomNotification(notification) => on_custom(notification),
+            _ => on_standard(self),
+        }
+    }
 }
 
 //*******************************//
@@ -1545,6 +2897,15 @@ impl ServerJsonrpcResponse {
     pub fn jsonrpc(&self) -> &::std::string::String {
         &self.jsonrpc
     }
+
+    /// Returns the id of the request this response belongs to.
+    pub fn id(&self) -> &RequestId {
+        &self.id
+    }
+    /// Returns an owned copy of the response id, convenient for moving into async tasks.
+    pub fn clone_id(&self) -> RequestId {
+        self.id.clone()
+    }
 }
 
 /// Formats the ServerJsonrpcResponse as a JSON string.
@@ -1589,6 +2950,70 @@ pub enum ResultFromServer {
     GetTaskPayloadResult(GetTaskPayloadResult),
 }
 
+impl ResultFromServer {
+    fn meta_field(&self) -> &Option<serde_json::Map<String, Value>> {
+        match self {
+            ResultFromServer::InitializeResult(result) => &result.meta,
+            ResultFromServer::ListResourcesResult(result) => &result.meta,
+            ResultFromServer::ListResourceTemplatesResult(result) => &result.meta,
+            ResultFromServer::ReadResourceResult(result) => &result.meta,
+            ResultFromServer::ListPromptsResult(result) => &result.meta,
+            ResultFromServer::GetPromptResult(result) => &result.meta,
+            ResultFromServer::ListToolsResult(result) => &result.meta,
+            ResultFromServer::CallToolResult(result) => &result.meta,
+            ResultFromServer::GetTaskResult(result) => &result.meta,
+            ResultFromServer::CancelTaskResult(result) => &result.meta,
+            ResultFromServer::ListTasksResult(result) => &result.meta,
+            ResultFromServer::CompleteResult(result) => &result.meta,
+            ResultFromServer::CreateTaskResult(result) => &result.meta,
+            ResultFromServer::Result(result) => &result.meta,
+            ResultFromServer::GetTaskPayloadResult(result) => &result.meta,
+        }
+    }
+
+    fn meta_field_mut(&mut self) -> &mut Option<serde_json::Map<String, Value>> {
+        match self {
+            ResultFromServer::InitializeResult(result) => &mut result.meta,
+            ResultFromServer::ListResourcesResult(result) => &mut result.meta,
+            ResultFromServer::ListResourceTemplatesResult(result) => &mut result.meta,
+            ResultFromServer::ReadResourceResult(result) => &mut result.meta,
+            ResultFromServer::ListPromptsResult(result) => &mut result.meta,
+            ResultFromServer::GetPromptResult(result) => &mut result.meta,
+            ResultFromServer::ListToolsResult(result) => &mut result.meta,
+            ResultFromServer::CallToolResult(result) => &mut result.meta,
+            ResultFromServer::GetTaskResult(result) => &mut result.meta,
+            ResultFromServer::CancelTaskResult(result) => &mut result.meta,
+            ResultFromServer::ListTasksResult(result) => &mut result.meta,
+            ResultFromServer::CompleteResult(result) => &mut result.meta,
+            ResultFromServer::CreateTaskResult(result) => &mut result.meta,
+            ResultFromServer::Result(result) => &mut result.meta,
+            ResultFromServer::GetTaskPayloadResult(result) => &mut result.meta,
+        }
+    }
+
+    /// Returns the `_meta` object of the result, if any.
+    pub fn meta(&self) -> Option<serde_json::Map<String, Value>> {
+        self.meta_field().clone()
+    }
+
+    /// Adds the given entries to the result `_meta`, overwriting existing keys.
+    pub fn extend_meta(&mut self, entries: &serde_json::Map<String, Value>) {
+        self.meta_field_mut()
+            .get_or_insert_with(serde_json::Map::new)
+            .extend(entries.clone());
+    }
+}
+
+/// Copies the `_meta` of a client request into the `_meta` of the server result answering it.
+///
+/// Useful for propagating tracing context (e.g. `traceparent`) through proxies.
+/// Entries already present in the result `_meta` are overwritten by the request ones.
+pub fn copy_meta(from: &RequestFromClient, to: &mut ResultFromServer) {
+    if let Some(meta) = from.meta() {
+        to.extend_meta(&meta);
+    }
+}
+
 //***************************//
 //** impl for JsonrpcErrorResponse **//
 //***************************//
@@ -1745,35 +3170,166 @@ impl FromMessage<MessageFromServer> for ServerMessage {
     }
 }
 
-//**************************//
-//**  MessageFromClient   **//
-//**************************//
-
-/// An enum representing various types of messages that can be sent from an MCP Client.
-/// It provides a typed structure for the message payload while skipping internal details like
-/// `requestId` and protocol version, which are used solely by the transport layer and
-/// do not need to be exposed to the user.
-#[derive(::serde::Serialize, ::serde::Deserialize, Clone, Debug)]
-#[serde(untagged)]
-pub enum MessageFromClient {
-    RequestFromClient(RequestFromClient),
-    ResultFromClient(ResultFromClient),
-    NotificationFromClient(NotificationFromClient),
-    Error(RpcError),
+/// Builds a server response or error for a request, with the request id fixed at construction.
+///
+/// Unlike [`FromMessage::from_message`], a missing id cannot be expressed, so building the
+/// message cannot fail.
+#[derive(Debug, Clone)]
+pub struct ResponseBuilder {
+    id: RequestId,
 }
 
-impl MessageFromClient {
-    /// Returns `true` if the message is an `InitializeRequest`.
-    pub fn is_initialize_request(&self) -> bool {
-        matches!(self, Self::RequestFromClient(RequestFromClient::InitializeRequest(_)))
+impl ResponseBuilder {
+    pub fn new(id: RequestId) -> Self {
+        Self { id }
     }
 
-    /// Returns `true` if the message is an `InitializedNotification`
-    pub fn is_initialized_notification(&self) -> bool {
-        matches!(
-            self,
-            Self::NotificationFromClient(NotificationFromClient::InitializedNotification(_))
-        )
+    /// Builds a successful response carrying `result`.
+    pub fn result(self, result: impl Into<ResultFromServer>) -> ServerMessage {
+        ServerMessage::Response(ServerJsonrpcResponse::new(self.id, result.into()))
+    }
+
+    /// Builds an error response carrying `error`.
+    pub fn error(self, error: RpcError) -> ServerMessage {
+        ServerMessage::Error(JsonrpcErrorResponse::new(error, Some(self.id)))
+    }
+}
+
+/// Builds a server notification. Notifications never carry an id, and this builder offers no way to set one.
+///
+/// ```compile_fail
+/// use rust_mcp_schema::schema_utils::NotificationBuilder;
+/// use rust_mcp_schema::RequestId;
+///
+/// let builder = NotificationBuilder::new().id(RequestId::Integer(1));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct NotificationBuilder;
+
+impl NotificationBuilder {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Builds a notification message carrying `notification`.
+    pub fn notification(self, notification: NotificationFromServer) -> ServerMessage {
+        ServerMessage::Notification(ServerJsonrpcNotification::new(notification))
+    }
+}
+
+/// Incrementally builds a `ClientJsonrpcRequest` for a custom method that is not modeled by the schema.
+///
+/// Created with [`ClientJsonrpcRequest::custom`]; each call to [`param`](Self::param) adds a field to the
+/// request's `params` object.
+#[derive(Debug, Clone)]
+pub struct ClientCustomRequestBuilder {
+    id: RequestId,
+    method: String,
+    params: serde_json::Map<String, Value>,
+}
+
+impl ClientCustomRequestBuilder {
+    /// Adds `key` with `value` to the request params, replacing any previous value for the same key.
+    pub fn param(mut self, key: impl Into<String>, value: impl Into<Value>) -> Self {
+        self.params.insert(key.into(), value.into());
+        self
+    }
+
+    /// Builds the request. The `params` member is omitted when no param was added.
+    pub fn build(self) -> ClientJsonrpcRequest {
+        let params = (!self.params.is_empty()).then_some(self.params);
+        ClientJsonrpcRequest::new(
+            self.id,
+            RequestFromClient::CustomRequest(CustomRequest {
+                method: self.method,
+                params,
+            }),
+        )
+    }
+}
+
+impl ClientJsonrpcRequest {
+    /// Starts building a request for the custom `method`, sent with the given `id`.
+    pub fn custom(id: RequestId, method: impl Into<String>) -> ClientCustomRequestBuilder {
+        ClientCustomRequestBuilder {
+            id,
+            method: method.into(),
+            params: serde_json::Map::new(),
+        }
+    }
+}
+
+/// Incrementally builds a `ServerJsonrpcRequest` for a custom method that is not modeled by the schema.
+///
+/// Created with [`ServerJsonrpcRequest::custom`]; each call to [`param`](Self::param) adds a field to the
+/// request's `params` object.
+#[derive(Debug, Clone)]
+pub struct ServerCustomRequestBuilder {
+    id: RequestId,
+    method: String,
+    params: serde_json::Map<String, Value>,
+}
+
+impl ServerCustomRequestBuilder {
+    /// Adds `key` with `value` to the request params, replacing any previous value for the same key.
+    pub fn param(mut self, key: impl Into<String>, value: impl Into<Value>) -> Self {
+        self.params.insert(key.into(), value.into());
+        self
+    }
+
+    /// Builds the request. The `params` member is omitted when no param was added.
+    pub fn build(self) -> ServerJsonrpcRequest {
+        let params = (!self.params.is_empty()).then_some(self.params);
+        ServerJsonrpcRequest::new(
+            self.id,
+            RequestFromServer::CustomRequest(CustomRequest {
+                method: self.method,
+                params,
+            }),
+        )
+    }
+}
+
+impl ServerJsonrpcRequest {
+    /// Starts building a request for the custom `method`, sent with the given `id`.
+    pub fn custom(id: RequestId, method: impl Into<String>) -> ServerCustomRequestBuilder {
+        ServerCustomRequestBuilder {
+            id,
+            method: method.into(),
+            params: serde_json::Map::new(),
+        }
+    }
+}
+
+//**************************//
+//**  MessageFromClient   **//
+//**************************//
+
+/// An enum representing various types of messages that can be sent from an MCP Client.
+/// It provides a typed structure for the message payload while skipping internal details like
+/// `requestId` and protocol version, which are used solely by the transport layer and
+/// do not need to be exposed to the user.
+#[derive(::serde::Serialize, ::serde::Deserialize, Clone, Debug)]
+#[serde(untagged)]
+pub enum MessageFromClient {
+    RequestFromClient(RequestFromClient),
+    ResultFromClient(ResultFromClient),
+    NotificationFromClient(NotificationFromClient),
+    Error(RpcError),
+}
+
+impl MessageFromClient {
+    /// Returns `true` if the message is an `InitializeRequest`.
+    pub fn is_initialize_request(&self) -> bool {
+        matches!(self, Self::RequestFromClient(RequestFromClient::InitializeRequest(_)))
+    }
+
+    /// Returns `true` if the message is an `InitializedNotification`
+    pub fn is_initialized_notification(&self) -> bool {
+        matches!(
+            self,
+            Self::NotificationFromClient(NotificationFromClient::InitializedNotification(_))
+        )
     }
 }
 
@@ -1974,10 +3530,66 @@ impl CallToolError {
 ///
 /// The conversion creates an internal error variant of `RpcError`
 /// and attaches the string representation of the original `CallToolError` as a message.
+/// The messages of the whole `source()` chain, outermost first, are attached as `data`.
 ///
 impl From<CallToolError> for RpcError {
     fn from(value: CallToolError) -> Self {
-        Self::internal_error().with_message(value.to_string())
+        let mut chain = vec![value.to_string()];
+        let mut source = value.0.source();
+        while let Some(error) = source {
+            chain.push(error.to_string());
+            source = error.source();
+        }
+        Self::internal_error()
+            .with_message(value.to_string())
+            .with_data(Some(json!(chain)))
+    }
+}
+
+/// A `tools/call` request paired with the id it must be answered with.
+///
+/// Handing a `ToolCall` to a tool handler instead of the bare request lets it build the response
+/// through [`ToolCall::respond`] without tracking the request id separately.
+#[derive(Debug, Clone)]
+pub struct ToolCall {
+    id: RequestId,
+    request: CallToolRequest,
+}
+
+impl ToolCall {
+    /// Creates a `ToolCall` answering `request`, using the id the request was sent with.
+    pub fn new(request: CallToolRequest) -> Self {
+        Self {
+            id: request.id.clone(),
+            request,
+        }
+    }
+
+    /// Returns the id the response will be sent with.
+    pub fn id(&self) -> &RequestId {
+        &self.id
+    }
+
+    /// Returns the underlying `tools/call` request.
+    pub fn request(&self) -> &CallToolRequest {
+        &self.request
+    }
+
+    /// Returns the name of the tool being called.
+    pub fn tool_name(&self) -> &str {
+        &self.request.params.name
+    }
+
+    /// Consumes the call and builds the `ServerMessage` response carrying `result` under the request id.
+    /// A `CallToolError` can be passed directly and is reported as an error result.
+    pub fn respond(self, result: impl Into<CallToolResult>) -> ServerMessage {
+        ServerMessage::new_response(self.id, result.into())
+    }
+}
+
+impl From<CallToolRequest> for ToolCall {
+    fn from(request: CallToolRequest) -> Self {
+        Self::new(request)
     }
 }
 
@@ -2008,6 +3620,14 @@ impl std::error::Error for CallToolError {
     }
 }
 
+/// Boxes an `anyhow::Error` into a `CallToolError`, keeping its context chain reachable through `source()`.
+#[cfg(feature = "anyhow")]
+impl From<anyhow::Error> for CallToolError {
+    fn from(error: anyhow::Error) -> Self {
+        CallToolError(Box::<dyn std::error::Error + Send + Sync>::from(error))
+    }
+}
+
 impl CallToolRequest {
     /// Retrieves the name of the tool from the request parameters.
     ///
@@ -2019,6 +3639,89 @@ impl CallToolRequest {
     pub fn tool_name(&self) -> &str {
         &self.params.name
     }
+
+    /// Returns `true` if the request calls the tool named `name`.
+    pub fn is_for_tool(&self, name: &str) -> bool {
+        self.params.name == name
+    }
+
+    /// Looks up the named argument in the request `arguments` and deserializes it into `T`.
+    ///
+    /// # Errors
+    /// Returns an `invalid_params` error if the argument is missing or cannot be deserialized into `T`.
+    pub fn argument<T: ::serde::de::DeserializeOwned>(&self, name: &str) -> std::result::Result<T, RpcError> {
+        let value = self
+            .params
+            .arguments
+            .as_ref()
+            .and_then(|arguments| arguments.get(name))
+            .ok_or_else(|| RpcError::invalid_params().with_message(format!("Missing argument '{name}'")))?;
+        T::deserialize(value)
+            .map_err(|err| RpcError::invalid_params().with_message(format!("Invalid argument '{name}': {err}")))
+    }
+
+    /// Returns `true` if the request `arguments` contain `name`.
+    pub fn has_argument(&self, name: &str) -> bool {
+        self.params
+            .arguments
+            .as_ref()
+            .is_some_and(|arguments| arguments.contains_key(name))
+    }
+
+    /// Checks that all of the given arguments are present in the request.
+    ///
+    /// # Errors
+    /// Returns an `invalid_params` error listing every missing argument.
+    pub fn require_arguments(&self, names: &[&str]) -> std::result::Result<(), RpcError> {
+        let missing: Vec<&str> = names.iter().copied().filter(|name| !self.has_argument(name)).collect();
+        if missing.is_empty() {
+            return Ok(());
+        }
+        Err(RpcError::invalid_params()
+            .with_message(format!("Missing arguments: {}", missing.join(", ")))
+            .with_data(Some(json!({ "missing" : missing }))))
+    }
+
+    /// Deserializes the whole request `arguments` object into `T`.
+    /// Missing `arguments` are treated as an empty object.
+    ///
+    /// # Errors
+    /// Returns an `invalid_params` error if the arguments cannot be deserialized into `T`.
+    pub fn arguments<T: ::serde::de::DeserializeOwned>(&self) -> std::result::Result<T, RpcError> {
+        let arguments = Value::Object(self.params.arguments.clone().unwrap_or_default());
+        serde_json::from_value(arguments).map_err(|err| {
+            RpcError::invalid_params().with_message(format!("Invalid arguments for tool '{}': {err}", self.params.name))
+        })
+    }
+
+    /// Suggests a timeout for the tool call by writing `_meta.timeoutMs`.
+    ///
+    /// This is a client-side convention, not part of the schema; servers are free to ignore it.
+    /// It is only available for this schema version: the draft `_meta` of tool calls is typed as
+    /// `RequestMetaObject`, which has no room for extra entries, and older versions define no
+    /// `_meta` on tool call params at all.
+    pub fn with_timeout_hint(mut self, duration: std::time::Duration) -> Self {
+        let timeout_ms = u64::try_from(duration.as_millis()).unwrap_or(u64::MAX);
+        self.params
+            .meta
+            .get_or_insert_with(CallToolMeta::default)
+            .extra
+            .get_or_insert_with(serde_json::Map::new)
+            .insert("timeoutMs".to_string(), Value::from(timeout_ms));
+        self
+    }
+
+    /// Returns the timeout suggested by the client through `_meta.timeoutMs`, if any.
+    pub fn timeout_hint(&self) -> Option<std::time::Duration> {
+        self.params
+            .meta
+            .as_ref()?
+            .extra
+            .as_ref()?
+            .get("timeoutMs")?
+            .as_u64()
+            .map(std::time::Duration::from_millis)
+    }
 }
 
 impl<T: Into<String>> From<T> for TextContent {
@@ -2051,6 +3754,11 @@ impl TextResourceContents {
         self.uri = uri.into();
         self
     }
+
+    /// Returns the size of the text in bytes, as encoded in UTF-8.
+    pub fn byte_len(&self) -> usize {
+        self.text.len()
+    }
 }
 
 impl BlobResourceContents {
@@ -2075,6 +3783,14 @@ impl BlobResourceContents {
         self.uri = uri.into();
         self
     }
+
+    /// Returns the size of the decoded blob in bytes, computed from the base64 length without decoding it.
+    /// Returns an `invalid_params` error if `blob` is not valid base64.
+    pub fn byte_len(&self) -> std::result::Result<usize, RpcError> {
+        base64_decoded_len(&self.blob).ok_or_else(|| {
+            RpcError::invalid_params().with_message("Invalid base64 data in BlobResourceContents".to_string())
+        })
+    }
 }
 
 #[derive(serde::Serialize, serde::Deserialize, Debug, Clone)]
@@ -2111,6 +3827,42 @@ impl ClientMessages {
             ClientMessages::Batch(client_messages) => Ok(client_messages),
         }
     }
+
+    /// Formats the ClientMessages as a pretty-printed JSON string, for debugging.
+    /// `Display` stays compact for use on the wire.
+    pub fn to_pretty_string(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap_or_else(|err| format!("Serialization error: {err}"))
+    }
+
+    /// Splits the messages into requests, notifications, responses and errors, preserving their order.
+    ///
+    /// A single message yields one non-empty bucket.
+    pub fn partition(
+        self,
+    ) -> (
+        Vec<ClientJsonrpcRequest>,
+        Vec<ClientJsonrpcNotification>,
+        Vec<ClientJsonrpcResponse>,
+        Vec<JsonrpcErrorResponse>,
+    ) {
+        let messages = match self {
+            ClientMessages::Single(client_message) => vec![client_message],
+            ClientMessages::Batch(client_messages) => client_messages,
+        };
+        let mut requests = Vec::new();
+        let mut notifications = Vec::new();
+        let mut responses = Vec::new();
+        let mut errors = Vec::new();
+        for message in messages {
+            match message {
+                ClientMessage::Request(request) => requests.push(request),
+                ClientMessage::Notification(notification) => notifications.push(notification),
+                ClientMessage::Response(response) => responses.push(response),
+                ClientMessage::Error(error) => errors.push(error),
+            }
+        }
+        (requests, notifications, responses, errors)
+    }
 }
 
 impl From<ClientMessage> for ClientMessages {
@@ -2125,6 +3877,35 @@ impl From<Vec<ClientMessage>> for ClientMessages {
     }
 }
 
+/// Appends messages, promoting a `Single` to a `Batch` when at least one message is added.
+impl Extend<ClientMessage> for ClientMessages {
+    fn extend<I: IntoIterator<Item = ClientMessage>>(&mut self, iter: I) {
+        let mut iter = iter.into_iter().peekable();
+        if iter.peek().is_none() {
+            return;
+        }
+        let mut messages = match std::mem::replace(self, ClientMessages::Batch(Vec::new())) {
+            ClientMessages::Single(message) => vec![message],
+            ClientMessages::Batch(messages) => messages,
+        };
+        messages.extend(iter);
+        *self = ClientMessages::Batch(messages);
+    }
+}
+
+/// Iterates over the messages without consuming them; a single message yields one item.
+impl<'a> IntoIterator for &'a ClientMessages {
+    type Item = &'a ClientMessage;
+    type IntoIter = std::slice::Iter<'a, ClientMessage>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        match self {
+            ClientMessages::Single(message) => std::slice::from_ref(message).iter(),
+            ClientMessages::Batch(messages) => messages.iter(),
+        }
+    }
+}
+
 impl Display for ClientMessages {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
@@ -2169,6 +3950,81 @@ impl ServerMessages {
             ServerMessages::Batch(server_messages) => Ok(server_messages),
         }
     }
+
+    /// Formats the ServerMessages as a pretty-printed JSON string, for debugging.
+    /// `Display` stays compact for use on the wire.
+    pub fn to_pretty_string(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap_or_else(|err| format!("Serialization error: {err}"))
+    }
+
+    /// Splits the messages into requests, notifications, responses and errors, preserving their order.
+    ///
+    /// A single message yields one non-empty bucket.
+    pub fn partition(
+        self,
+    ) -> (
+        Vec<ServerJsonrpcRequest>,
+        Vec<ServerJsonrpcNotification>,
+        Vec<ServerJsonrpcResponse>,
+        Vec<JsonrpcErrorResponse>,
+    ) {
+        let messages = match self {
+            ServerMessages::Single(server_message) => vec![server_message],
+            ServerMessages::Batch(server_messages) => server_messages,
+        };
+        let mut requests = Vec::new();
+        let mut notifications = Vec::new();
+        let mut responses = Vec::new();
+        let mut errors = Vec::new();
+        for message in messages {
+            match message {
+                ServerMessage::Request(request) => requests.push(request),
+                ServerMessage::Notification(notification) => notifications.push(notification),
+                ServerMessage::Response(response) => responses.push(response),
+                ServerMessage::Error(error) => errors.push(error),
+            }
+        }
+        (requests, notifications, responses, errors)
+    }
+
+    /// Builds a batch of `count` identical notifications, e.g. to fan the same notification out to
+    /// several sessions. The notification is constructed once and cloned for each entry.
+    pub fn broadcast(notification: NotificationFromServer, count: usize) -> Self {
+        let message = ServerMessage::Notification(ServerJsonrpcNotification::new(notification));
+        ServerMessages::Batch(vec![message; count])
+    }
+
+    /// Reorders a batch of responses to follow the order of the requests in `requests`.
+    ///
+    /// Responses and errors are matched to requests by id. Messages without a matching request,
+    /// including server-initiated requests and notifications, keep their relative order and are moved to the end.
+    pub fn ordered_for(self, requests: &ClientMessages) -> ServerMessages {
+        let ServerMessages::Batch(mut messages) = self else {
+            return self;
+        };
+        let requests = match requests {
+            ClientMessages::Single(message) => std::slice::from_ref(message),
+            ClientMessages::Batch(messages) => messages.as_slice(),
+        };
+        let positions: std::collections::HashMap<&RequestId, usize> = requests
+            .iter()
+            .filter(|message| message.is_request())
+            .filter_map(|message| message.request_id())
+            .enumerate()
+            .map(|(position, id)| (id, position))
+            .collect();
+        messages.sort_by_key(|message| {
+            if message.is_response() || message.is_error() {
+                message
+                    .request_id()
+                    .and_then(|id| positions.get(id).copied())
+                    .unwrap_or(usize::MAX)
+            } else {
+                usize::MAX
+            }
+        });
+        ServerMessages::Batch(messages)
+    }
 }
 
 impl From<ServerMessage> for ServerMessages {
@@ -2183,6 +4039,35 @@ impl From<Vec<ServerMessage>> for ServerMessages {
     }
 }
 
+/// Appends messages, promoting a `Single` to a `Batch` when at least one message is added.
+impl Extend<ServerMessage> for ServerMessages {
+    fn extend<I: IntoIterator<Item = ServerMessage>>(&mut self, iter: I) {
+        let mut iter = iter.into_iter().peekable();
+        if iter.peek().is_none() {
+            return;
+        }
+        let mut messages = match std::mem::replace(self, ServerMessages::Batch(Vec::new())) {
+            ServerMessages::Single(message) => vec![message],
+            ServerMessages::Batch(messages) => messages,
+        };
+        messages.extend(iter);
+        *self = ServerMessages::Batch(messages);
+    }
+}
+
+/// Iterates over the messages without consuming them; a single message yields one item.
+impl<'a> IntoIterator for &'a ServerMessages {
+    type Item = &'a ServerMessage;
+    type IntoIter = std::slice::Iter<'a, ServerMessage>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        match self {
+            ServerMessages::Single(message) => std::slice::from_ref(message).iter(),
+            ServerMessages::Batch(messages) => messages.iter(),
+        }
+    }
+}
+
 impl Display for ServerMessages {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
@@ -2193,6 +4078,30 @@ impl Display for ServerMessages {
     }
 }
 
+/// A message wrapped with a session id and sequence number for transports that need framing.
+///
+/// This is an opt-in interop envelope, not part of the MCP protocol.
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Framed<T> {
+    pub session_id: String,
+    pub seq: u64,
+    pub message: T,
+}
+
+impl<T> Framed<T> {
+    pub fn new(session_id: impl Into<String>, seq: u64, message: T) -> Self {
+        Self {
+            session_id: session_id.into(),
+            seq,
+            message,
+        }
+    }
+}
+
+pub type FramedClientMessage = Framed<ClientMessage>;
+pub type FramedServerMessage = Framed<ServerMessage>;
+
 #[derive(serde::Serialize, serde::Deserialize, Debug, Clone)]
 #[serde(untagged)]
 #[allow(clippy::large_enum_variant)]
@@ -2241,6 +4150,22 @@ impl From<Vec<MessageFromServer>> for MessagesFromServer {
     }
 }
 
+/// Appends messages, promoting a `Single` to a `Batch` when at least one message is added.
+impl Extend<MessageFromServer> for MessagesFromServer {
+    fn extend<I: IntoIterator<Item = MessageFromServer>>(&mut self, iter: I) {
+        let mut iter = iter.into_iter().peekable();
+        if iter.peek().is_none() {
+            return;
+        }
+        let mut messages = match std::mem::replace(self, MessagesFromServer::Batch(Vec::new())) {
+            MessagesFromServer::Single(message) => vec![message],
+            MessagesFromServer::Batch(messages) => messages,
+        };
+        messages.extend(iter);
+        *self = MessagesFromServer::Batch(messages);
+    }
+}
+
 #[derive(serde::Serialize, serde::Deserialize, Debug, Clone)]
 #[serde(untagged)]
 #[allow(clippy::large_enum_variant)]
@@ -2289,6 +4214,22 @@ impl From<Vec<MessageFromClient>> for MessagesFromClient {
     }
 }
 
+/// Appends messages, promoting a `Single` to a `Batch` when at least one message is added.
+impl Extend<MessageFromClient> for MessagesFromClient {
+    fn extend<I: IntoIterator<Item = MessageFromClient>>(&mut self, iter: I) {
+        let mut iter = iter.into_iter().peekable();
+        if iter.peek().is_none() {
+            return;
+        }
+        let mut messages = match std::mem::replace(self, MessagesFromClient::Batch(Vec::new())) {
+            MessagesFromClient::Single(message) => vec![message],
+            MessagesFromClient::Batch(messages) => messages,
+        };
+        messages.extend(iter);
+        *self = MessagesFromClient::Batch(messages);
+    }
+}
+
 #[derive(Debug)]
 pub struct StringSchemaFormatError {
     invalid_value: String,
@@ -2531,6 +4472,34 @@ impl ElicitRequestFormParams {
     }
 }
 
+/// Capabilities both peers of a session advertise, as computed by [`ServerCapabilities::negotiate`].
+///
+/// Every flag is the intersection of the two advertisements exchanged during `initialize`: it is `true`
+/// only when the server and the client both declare the capability under the same name. Capabilities
+/// that only one side declares, or that this schema version does not define, are `false`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct NegotiatedCapabilities {
+    pub tools: bool,
+    pub prompts: bool,
+    pub resources: bool,
+    pub resources_subscribe: bool,
+    pub logging: bool,
+    pub completions: bool,
+    pub sampling: bool,
+    pub roots: bool,
+    pub elicitation: bool,
+    pub tasks: bool,
+    /// Names of the `experimental` capabilities declared by both peers, sorted.
+    pub experimental: Vec<String>,
+}
+
+/// Returns `true` if the capability at `path` is declared, i.e. present and neither `null` nor `false`.
+fn capability_declared(capabilities: &Value, path: &[&str]) -> bool {
+    path.iter()
+        .try_fold(capabilities, |value, key| value.get(key))
+        .is_some_and(|value| !value.is_null() && *value != Value::Bool(false))
+}
+
 impl ServerCapabilities {
     /// Returns `true` if the server supports listing tasks.
     ///
@@ -2660,6 +4629,40 @@ impl ServerCapabilities {
 
         Ok(())
     }
+
+    /// Computes the capabilities both peers declare, intersecting these server capabilities with `client`.
+    ///
+    /// See [`NegotiatedCapabilities`] for how each capability is matched.
+    pub fn negotiate(&self, client: &ClientCapabilities) -> NegotiatedCapabilities {
+        let server = serde_json::to_value(self).unwrap_or_default();
+        let client = serde_json::to_value(client).unwrap_or_default();
+        let both = |path: &[&str]| capability_declared(&server, path) && capability_declared(&client, path);
+        let mut experimental: Vec<String> = server
+            .get("experimental")
+            .and_then(Value::as_object)
+            .map(|experimental| {
+                experimental
+                    .keys()
+                    .filter(|name| both(&["experimental", name.as_str()]))
+                    .cloned()
+                    .collect()
+            })
+            .unwrap_or_default();
+        experimental.sort();
+        NegotiatedCapabilities {
+            tools: both(&["tools"]),
+            prompts: both(&["prompts"]),
+            resources: both(&["resources"]),
+            resources_subscribe: both(&["resources", "subscribe"]),
+            logging: both(&["logging"]),
+            completions: both(&["completions"]),
+            sampling: both(&["sampling"]),
+            roots: both(&["roots"]),
+            elicitation: both(&["elicitation"]),
+            tasks: both(&["tasks"]),
+            experimental,
+        }
+    }
 }
 
 impl ServerTasks {
@@ -3067,7 +5070,1543 @@ impl FromStr for Role {
     }
 }
 
-pub type CustomNotification = CustomRequest;
+/// Strips the padding of a standard (RFC 4648) base64 string.
+/// Returns `None` if the length of the input or of its padding is invalid.
+fn base64_data(input: &str) -> Option<&str> {
+    let data = input.trim_end_matches('=');
+    let padding = input.len() - data.len();
+    if data.len() % 4 == 1 || padding > 2 || (padding > 0 && input.len() % 4 != 0) {
+        return None;
+    }
+    Some(data)
+}
+
+/// Returns the number of bytes a standard base64 string decodes to, computed from its length without padding.
+/// Returns `None` if the input contains characters outside the base64 alphabet or has an invalid length.
+fn base64_decoded_len(input: &str) -> Option<usize> {
+    let data = base64_data(input)?;
+    if !data
+        .bytes()
+        .all(|byte| byte.is_ascii_alphanumeric() || byte == b'+' || byte == b'/')
+    {
+        return None;
+    }
+    // every 4 characters encode 3 bytes, a trailing group of 2 or 3 characters encodes 1 or 2 bytes
+    Some(data.len() * 3 / 4)
+}
+
+/// Decodes a standard (RFC 4648) base64 string, with or without padding.
+/// Returns `None` if the input contains characters outside the base64 alphabet or has an invalid length.
+fn decode_base64(input: &str) -> Option<Vec<u8>> {
+    let data = base64_data(input)?;
+    let mut decoded = Vec::with_capacity(data.len() * 3 / 4);
+    let mut buffer: u32 = 0;
+    let mut bits = 0;
+    for byte in data.bytes() {
+        let value = match byte {
+            b'A'..=b'Z' => byte - b'A',
+            b'a'..=b'z' => byte - b'a' + 26,
+            b'0'..=b'9' => byte - b'0' + 52,
+            b'+' => 62,
+            b'/' => 63,
+            _ => return None,
+        };
+        buffer = (buffer << 6) | u32::from(value);
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            decoded.push((buffer >> bits) as u8);
+            buffer &= (1 << bits) - 1;
+        }
+    }
+    Some(decoded)
+}
+
+impl ImageContent {
+    /// Decodes the base64 encoded image `data` into raw bytes.
+    /// Returns an `invalid_params` error if `data` is not valid base64.
+    pub fn decode_data(&self) -> std::result::Result<Vec<u8>, RpcError> {
+        decode_base64(&self.data)
+            .ok_or_else(|| RpcError::invalid_params().with_message("Invalid base64 data in ImageContent".to_string()))
+    }
+}
+
+impl AudioContent {
+    /// Decodes the base64 encoded audio `data` into raw bytes.
+    /// Returns an `invalid_params` error if `data` is not valid base64.
+    pub fn decode_data(&self) -> std::result::Result<Vec<u8>, RpcError> {
+        decode_base64(&self.data)
+            .ok_or_else(|| RpcError::invalid_params().with_message("Invalid base64 data in AudioContent".to_string()))
+    }
+}
+
+/// Equality and hashing of typed requests consider only the `method` and `params`, not the request id,
+/// so identical requests sent under different ids compare equal (e.g. to dedupe in-flight requests).
+/// Params are compared structurally, so objects whose keys are ordered differently are equal and
+/// hash the same.
+impl PartialEq for ClientRequest {
+    fn eq(&self, other: &Self) -> bool {
+        request_identity(self) == request_identity(other)
+    }
+}
+
+impl Eq for ClientRequest {}
+
+impl Hash for ClientRequest {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        hash_canonical_json(&request_identity(self), state);
+    }
+}
+
+/// See the `PartialEq` implementation of `ClientRequest`: only `method` and `params` are compared.
+impl PartialEq for ServerRequest {
+    fn eq(&self, other: &Self) -> bool {
+        request_identity(self) == request_identity(other)
+    }
+}
+
+impl Eq for ServerRequest {}
+
+impl Hash for ServerRequest {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        hash_canonical_json(&request_identity(self), state);
+    }
+}
+
+/// Serializes a message and drops its `jsonrpc` member.
+fn without_jsonrpc<T: serde::Serialize>(message: &T) -> Value {
+    let mut value = serde_json::to_value(message).unwrap_or_default();
+    if let Value::Object(map) = &mut value {
+        map.remove("jsonrpc");
+    }
+    value
+}
+
+/// Serializes a request and drops the transport-level `id` and `jsonrpc` members.
+fn request_identity<T: serde::Serialize>(request: &T) -> Value {
+    let mut value = serde_json::to_value(request).unwrap_or_default();
+    if let Value::Object(map) = &mut value {
+        map.remove("id");
+        map.remove("jsonrpc");
+    }
+    value
+}
+
+/// Hashes a JSON value with object keys visited in sorted order, independently of how the map is ordered.
+fn hash_canonical_json<H: Hasher>(value: &Value, state: &mut H) {
+    match value {
+        Value::Null => 0u8.hash(state),
+        Value::Bool(flag) => {
+            1u8.hash(state);
+            flag.hash(state);
+        }
+        Value::Number(number) => {
+            2u8.hash(state);
+            number.to_string().hash(state);
+        }
+        Value::String(text) => {
+            3u8.hash(state);
+            text.hash(state);
+        }
+        Value::Array(items) => {
+            4u8.hash(state);
+            items.len().hash(state);
+            items.iter().for_each(|item| hash_canonical_json(item, state));
+        }
+        Value::Object(map) => {
+            5u8.hash(state);
+            map.len().hash(state);
+            let mut entries: Vec<(&String, &Value)> = map.iter().collect();
+            entries.sort_by(|left, right| left.0.cmp(right.0));
+            for (key, value) in entries {
+                key.hash(state);
+                hash_canonical_json(value, state);
+            }
+        }
+    }
+}
+
+impl ClientRequest {
+    /// Returns the protocol method of the request (e.g. `tools/call`) as a `&'static str`.
+    ///
+    /// Unlike [`ClientRequest::method`], the returned string is not tied to the lifetime of the request,
+    /// so it can be stored or returned from match arms without allocating.
+    pub fn method_name(&self) -> &'static str {
+        match self {
+            ClientRequest::InitializeRequest(_) => InitializeRequest::method_value(),
+            ClientRequest::PingRequest(_) => PingRequest::method_value(),
+            ClientRequest::ListResourcesRequest(_) => ListResourcesRequest::method_value(),
+            ClientRequest::ListResourceTemplatesRequest(_) => ListResourceTemplatesRequest::method_value(),
+            ClientRequest::ReadResourceRequest(_) => ReadResourceRequest::method_value(),
+            ClientRequest::SubscribeRequest(_) => SubscribeRequest::method_value(),
+            ClientRequest::UnsubscribeRequest(_) => UnsubscribeRequest::method_value(),
+            ClientRequest::ListPromptsRequest(_) => ListPromptsRequest::method_value(),
+            ClientRequest::GetPromptRequest(_) => GetPromptRequest::method_value(),
+            ClientRequest::ListToolsRequest(_) => ListToolsRequest::method_value(),
+            ClientRequest::CallToolRequest(_) => CallToolRequest::method_value(),
+            ClientRequest::GetTaskRequest(_) => GetTaskRequest::method_value(),
+            ClientRequest::GetTaskPayloadRequest(_) => GetTaskPayloadRequest::method_value(),
+            ClientRequest::CancelTaskRequest(_) => CancelTaskRequest::method_value(),
+            ClientRequest::ListTasksRequest(_) => ListTasksRequest::method_value(),
+            ClientRequest::SetLevelRequest(_) => SetLevelRequest::method_value(),
+            ClientRequest::CompleteRequest(_) => CompleteRequest::method_value(),
+        }
+    }
+}
+
+impl From<&ClientRequest> for &'static str {
+    fn from(request: &ClientRequest) -> Self {
+        request.method_name()
+    }
+}
+
+/// Formats the ClientRequest as its method name (e.g. `tools/call`), for concise log lines.
+impl Display for ClientRequest {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.method())
+    }
+}
+
+/// Formats the ServerRequest as its method name (e.g. `sampling/createMessage`), for concise log lines.
+impl Display for ServerRequest {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.method())
+    }
+}
+
+/// Formats the ClientNotification as its method name (e.g. `notifications/initialized`), for concise log lines.
+impl Display for ClientNotification {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.method())
+    }
+}
+
+/// Formats the ServerNotification as its method name (e.g. `notifications/tools/list_changed`), for concise log lines.
+impl Display for ServerNotification {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.method())
+    }
+}
+
+impl PingRequest {
+    /// Creates a `PingRequest` with the given request id and no params.
+    pub fn without_params(id: RequestId) -> Self {
+        Self::new(id, None)
+    }
+}
+
+/// Creates a `PingRequest` without params and with id `0`.
+///
+/// Use [`PingRequest::without_params`] to give each request sent in a session its own id.
+impl Default for PingRequest {
+    fn default() -> Self {
+        Self::without_params(RequestId::Integer(0))
+    }
+}
+
+impl ListResourcesRequest {
+    /// Creates a `ListResourcesRequest` with the given request id and no params.
+    pub fn without_params(id: RequestId) -> Self {
+        Self::new(id, None)
+    }
+}
+
+/// Creates a `ListResourcesRequest` without params and with id `0`.
+///
+/// Use [`ListResourcesRequest::without_params`] to give each request sent in a session its own id.
+impl Default for ListResourcesRequest {
+    fn default() -> Self {
+        Self::without_params(RequestId::Integer(0))
+    }
+}
+
+impl ListPromptsRequest {
+    /// Creates a `ListPromptsRequest` with the given request id and no params.
+    pub fn without_params(id: RequestId) -> Self {
+        Self::new(id, None)
+    }
+}
+
+/// Creates a `ListPromptsRequest` without params and with id `0`.
+///
+/// Use [`ListPromptsRequest::without_params`] to give each request sent in a session its own id.
+impl Default for ListPromptsRequest {
+    fn default() -> Self {
+        Self::without_params(RequestId::Integer(0))
+    }
+}
+
+impl ListToolsRequest {
+    /// Creates a `ListToolsRequest` with the given request id and no params.
+    pub fn without_params(id: RequestId) -> Self {
+        Self::new(id, None)
+    }
+}
+
+/// Creates a `ListToolsRequest` without params and with id `0`.
+///
+/// Use [`ListToolsRequest::without_params`] to give each request sent in a session its own id.
+impl Default for ListToolsRequest {
+    fn default() -> Self {
+        Self::without_params(RequestId::Integer(0))
+    }
+}
+
+impl ResourceUpdatedNotification {
+    /// Creates a `ResourceUpdatedNotification` for the resource identified by `uri`.
+    pub fn for_uri(uri: impl Into<String>) -> Self {
+        Self::new(ResourceUpdatedNotificationParams {
+            meta: None,
+            uri: uri.into(),
+        })
+    }
+}
+
+/// Common accessors shared by text and blob resource contents.
+pub trait ResourceContentsExt {
+    /// The URI of the resource.
+    fn uri(&self) -> &str;
+    /// The MIME type of the resource, if known.
+    fn mime_type(&self) -> Option<&str>;
+}
+
+impl ResourceContentsExt for TextResourceContents {
+    fn uri(&self) -> &str {
+        &self.uri
+    }
+    fn mime_type(&self) -> Option<&str> {
+        self.mime_type.as_deref()
+    }
+}
+
+impl ResourceContentsExt for BlobResourceContents {
+    fn uri(&self) -> &str {
+        &self.uri
+    }
+    fn mime_type(&self) -> Option<&str> {
+        self.mime_type.as_deref()
+    }
+}
+
+impl ResourceContentsExt for EmbeddedResourceResource {
+    fn uri(&self) -> &str {
+        match self {
+            EmbeddedResourceResource::TextResourceContents(contents) => contents.uri(),
+            EmbeddedResourceResource::BlobResourceContents(contents) => contents.uri(),
+        }
+    }
+    fn mime_type(&self) -> Option<&str> {
+        match self {
+            EmbeddedResourceResource::TextResourceContents(contents) => contents.mime_type(),
+            EmbeddedResourceResource::BlobResourceContents(contents) => contents.mime_type(),
+        }
+    }
+}
+
+impl Annotations {
+    /// Sets the time the annotated resource was last modified, as an ISO 8601 string (e.g. `2025-01-12T15:00:58Z`).
+    pub fn with_last_modified(mut self, timestamp: impl Into<String>) -> Self {
+        self.last_modified = Some(timestamp.into());
+        self
+    }
+
+    /// Parses `last_modified` as an RFC 3339 timestamp, converted to UTC.
+    /// Returns `None` if the field is absent or not a valid timestamp.
+    #[cfg(feature = "chrono")]
+    pub fn last_modified_datetime(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        let timestamp = self.last_modified.as_deref()?;
+        chrono::DateTime::parse_from_rfc3339(timestamp)
+            .ok()
+            .map(|datetime| datetime.with_timezone(&chrono::Utc))
+    }
+}
+
+impl CallToolResult {
+    /// Creates a result from `content`, unless its serialized size exceeds `max_bytes`.
+    ///
+    /// Oversized content is replaced with a single text block, holding the text of the original text blocks
+    /// truncated to at most `max_bytes` bytes, and the result is flagged as an error.
+    pub fn capped(content: Vec<ContentBlock>, max_bytes: usize) -> Self {
+        let size = serde_json::to_vec(&content).map_or(usize::MAX, |bytes| bytes.len());
+        if size <= max_bytes {
+            return Self::from_content(content);
+        }
+        let text = content
+            .iter()
+            .filter_map(|item| item.as_text_content().ok())
+            .map(|text_content| text_content.text.as_str())
+            .collect::<Vec<_>>()
+            .join("\n");
+        let mut end = text.len().min(max_bytes);
+        while !text.is_char_boundary(end) {
+            end -= 1;
+        }
+        Self::from_content(vec![TextContent::new(text[..end].to_string(), None, None).into()]).with_is_error(true)
+    }
+
+    /// Counts the content items per content type (e.g. `text`, `image`, `resource`), for telemetry.
+    ///
+    /// The keys are the same values [`ContentBlock::content_type`] returns.
+    pub fn content_type_counts(&self) -> std::collections::HashMap<&'static str, usize> {
+        let mut counts = std::collections::HashMap::new();
+        for content in &self.content {
+            let content_type = match content {
+                ContentBlock::TextContent(_) => TextContent::type_value(),
+                ContentBlock::ImageContent(_) => ImageContent::type_value(),
+                ContentBlock::AudioContent(_) => AudioContent::type_value(),
+                ContentBlock::ResourceLink(_) => ResourceLink::type_value(),
+                ContentBlock::EmbeddedResource(_) => EmbeddedResource::type_value(),
+            };
+            *counts.entry(content_type).or_insert(0) += 1;
+        }
+        counts
+    }
+
+    /// Returns the value at the JSON Pointer (RFC 6901) `pointer` within `structuredContent`, e.g.
+    /// `/result/items/0`.
+    ///
+    /// Returns `None` if there is no structured content, the pointer does not resolve, or the pointer
+    /// is empty (the structured content itself is available as the `structured_content` field).
+    pub fn structured_get(&self, pointer: &str) -> Option<&Value> {
+        let structured_content = self.structured_content.as_ref()?;
+        let path = pointer.strip_prefix('/')?;
+        let (first, rest) = match path.find('/') {
+            Some(index) => (&path[..index], &path[index..]),
+            None => (path, ""),
+        };
+        let key = first.replace("~1", "/").replace("~0", "~");
+        structured_content.get(&key)?.pointer(rest)
+    }
+
+    /// Sets the `is_error` flag of the CallToolResult, leaving its content untouched.
+    pub fn with_is_error(mut self, is_error: bool) -> Self {
+        self.is_error = Some(is_error);
+        self
+    }
+}
+
+/// Builds a [`Tool`] for declaration in a `tools/list` result.
+#[derive(Debug, Clone)]
+pub struct ToolBuilder {
+    inner: Tool,
+}
+
+impl ToolBuilder {
+    pub fn new(name: impl Into<String>, input_schema: ToolInputSchema) -> Self {
+        Self {
+            inner: Tool {
+                annotations: None,
+                description: None,
+                execution: None,
+                icons: Vec::new(),
+                input_schema,
+                meta: None,
+                name: name.into(),
+                output_schema: None,
+                title: None,
+            },
+        }
+    }
+
+    /// Sets a human-readable title for display.
+    pub fn title(mut self, title: impl Into<String>) -> Self {
+        self.inner.title = Some(title.into());
+        self
+    }
+
+    /// Sets a description of what the tool does.
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.inner.description = Some(description.into());
+        self
+    }
+
+    /// Sets behavioral hints, e.g. built with [`ToolAnnotations::builder`].
+    pub fn annotations(mut self, annotations: ToolAnnotations) -> Self {
+        self.inner.annotations = Some(annotations);
+        self
+    }
+
+    /// Sets the schema of the tool's `structuredContent`.
+    pub fn output_schema(mut self, output_schema: ToolOutputSchema) -> Self {
+        self.inner.output_schema = Some(output_schema);
+        self
+    }
+
+    /// Sets the execution properties, such as task support.
+    pub fn execution(mut self, execution: ToolExecution) -> Self {
+        self.inner.execution = Some(execution);
+        self
+    }
+
+    /// Sets the icons clients may display.
+    pub fn icons(mut self, icons: Vec<Icon>) -> Self {
+        self.inner.icons = icons;
+        self
+    }
+
+    pub fn build(self) -> Tool {
+        self.inner
+    }
+}
+
+impl Tool {
+    /// Returns a [`ToolBuilder`] for a tool with the given name and input schema.
+    pub fn builder(name: impl Into<String>, input_schema: ToolInputSchema) -> ToolBuilder {
+        ToolBuilder::new(name, input_schema)
+    }
+}
+
+/// Builds a [`Prompt`] for declaration in a `prompts/list` result.
+#[derive(Debug, Clone)]
+pub struct PromptBuilder {
+    inner: Prompt,
+}
+
+impl PromptBuilder {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            inner: Prompt {
+                arguments: Vec::new(),
+                description: None,
+                icons: Vec::new(),
+                meta: None,
+                name: name.into(),
+                title: None,
+            },
+        }
+    }
+
+    /// Sets a human-readable title for display.
+    pub fn title(mut self, title: impl Into<String>) -> Self {
+        self.inner.title = Some(title.into());
+        self
+    }
+
+    /// Sets a description of what the prompt does.
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.inner.description = Some(description.into());
+        self
+    }
+
+    /// Adds an argument the prompt accepts.
+    pub fn argument(mut self, argument: PromptArgument) -> Self {
+        self.inner.arguments.push(argument);
+        self
+    }
+
+    /// Replaces the arguments the prompt accepts.
+    pub fn arguments(mut self, arguments: Vec<PromptArgument>) -> Self {
+        self.inner.arguments = arguments;
+        self
+    }
+
+    /// Sets the icons clients may display.
+    pub fn icons(mut self, icons: Vec<Icon>) -> Self {
+        self.inner.icons = icons;
+        self
+    }
+
+    pub fn build(self) -> Prompt {
+        self.inner
+    }
+}
+
+impl Prompt {
+    /// Returns a [`PromptBuilder`] for a prompt with the given name.
+    pub fn builder(name: impl Into<String>) -> PromptBuilder {
+        PromptBuilder::new(name)
+    }
+}
+
+/// Builds a [`Resource`] for declaration in a `resources/list` result.
+#[derive(Debug, Clone)]
+pub struct ResourceBuilder {
+    inner: Resource,
+}
+
+impl ResourceBuilder {
+    pub fn new(uri: impl Into<String>, name: impl Into<String>) -> Self {
+        Self {
+            inner: Resource {
+                annotations: None,
+                description: None,
+                icons: Vec::new(),
+                meta: None,
+                mime_type: None,
+                name: name.into(),
+                size: None,
+                title: None,
+                uri: uri.into(),
+            },
+        }
+    }
+
+    /// Sets a human-readable title for display.
+    pub fn title(mut self, title: impl Into<String>) -> Self {
+        self.inner.title = Some(title.into());
+        self
+    }
+
+    /// Sets a description of what the resource represents.
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.inner.description = Some(description.into());
+        self
+    }
+
+    /// Sets the MIME type of the resource, if known.
+    pub fn mime_type(mut self, mime_type: impl Into<String>) -> Self {
+        self.inner.mime_type = Some(mime_type.into());
+        self
+    }
+
+    /// Sets the size of the raw resource content in bytes, if known.
+    pub fn size(mut self, size: i64) -> Self {
+        self.inner.size = Some(size);
+        self
+    }
+
+    /// Sets annotations for the client.
+    pub fn annotations(mut self, annotations: Annotations) -> Self {
+        self.inner.annotations = Some(annotations);
+        self
+    }
+
+    /// Sets the icons clients may display.
+    pub fn icons(mut self, icons: Vec<Icon>) -> Self {
+        self.inner.icons = icons;
+        self
+    }
+
+    pub fn build(self) -> Resource {
+        self.inner
+    }
+}
+
+impl Resource {
+    /// Returns a [`ResourceBuilder`] for the resource at `uri`.
+    pub fn builder(uri: impl Into<String>, name: impl Into<String>) -> ResourceBuilder {
+        ResourceBuilder::new(uri, name)
+    }
+}
+
+impl ListResourcesResult {
+    /// Returns the resource with the given `uri`, if listed.
+    pub fn find_by_uri(&self, uri: &str) -> Option<&Resource> {
+        self.resources.iter().find(|resource| resource.uri == uri)
+    }
+}
+
+impl ListPromptsResult {
+    /// Returns the prompt with the given `name`, if listed.
+    pub fn find_by_name(&self, name: &str) -> Option<&Prompt> {
+        self.prompts.iter().find(|prompt| prompt.name == name)
+    }
+}
+
+impl ListToolsResult {
+    /// Returns the tool with the given `name`, if listed.
+    pub fn find_by_name(&self, name: &str) -> Option<&Tool> {
+        self.tools.iter().find(|tool| tool.name == name)
+    }
+}
+
+impl ReadResourceResult {
+    /// Returns `true` if every entry is text, i.e. the result can be rendered inline rather than offered
+    /// as a download. An empty result is considered text only.
+    pub fn is_text_only(&self) -> bool {
+        self.contents
+            .iter()
+            .all(|content| matches!(content, ReadResourceContent::TextResourceContents(_)))
+    }
+
+    /// Returns the total size in bytes of all entries, counting text as UTF-8 and blobs decoded.
+    /// Returns an `invalid_params` error if any blob is not valid base64.
+    pub fn total_bytes(&self) -> std::result::Result<usize, RpcError> {
+        self.contents.iter().try_fold(0, |total, content| {
+            let size = match content {
+                ReadResourceContent::TextResourceContents(text) => text.byte_len(),
+                ReadResourceContent::BlobResourceContents(blob) => blob.byte_len()?,
+            };
+            Ok(total + size)
+        })
+    }
+
+    /// Returns an iterator over the text entries, skipping binary blobs.
+    pub fn text_contents(&self) -> impl Iterator<Item = &TextResourceContents> {
+        self.contents.iter().filter_map(|content| match content {
+            ReadResourceContent::TextResourceContents(text) => Some(text),
+            ReadResourceContent::BlobResourceContents(_) => None,
+        })
+    }
+}
+
+/// Lists the differences between two server capability sets, one human-readable line per differing
+/// field (e.g. `tools.listChanged: true -> false`), to help debug unexpected handshakes.
+///
+/// Fields present on only one side are reported against `<absent>`. Returns an empty list when the
+/// capabilities are identical.
+pub fn capabilities_diff(a: &ServerCapabilities, b: &ServerCapabilities) -> Vec<String> {
+    let mut differences = Vec::new();
+    json_value_diff(
+        "",
+        &serde_json::to_value(a).unwrap_or_default(),
+        &serde_json::to_value(b).unwrap_or_default(),
+        &mut differences,
+    );
+    differences
+}
+
+/// Lists the differences between two client capability sets; see [`capabilities_diff`].
+pub fn client_capabilities_diff(a: &ClientCapabilities, b: &ClientCapabilities) -> Vec<String> {
+    let mut differences = Vec::new();
+    json_value_diff(
+        "",
+        &serde_json::to_value(a).unwrap_or_default(),
+        &serde_json::to_value(b).unwrap_or_default(),
+        &mut differences,
+    );
+    differences
+}
+
+/// Recursively compares two JSON values, descending into objects and recording differing leaves.
+fn json_value_diff(path: &str, a: &Value, b: &Value, differences: &mut Vec<String>) {
+    match (a, b) {
+        (Value::Object(left), Value::Object(right)) => {
+            let mut keys: Vec<&String> = left
+                .keys()
+                .chain(right.keys().filter(|key| !left.contains_key(*key)))
+                .collect();
+            keys.sort();
+            for key in keys {
+                let child_path = if path.is_empty() {
+                    key.to_string()
+                } else {
+                    format!("{path}.{key}")
+                };
+                match (left.get(key), right.get(key)) {
+                    (Some(left), Some(right)) => json_value_diff(&child_path, left, right, differences),
+                    (Some(left), None) => differences.push(format!("{child_path}: {left} -> <absent>")),
+                    (None, Some(right)) => differences.push(format!("{child_path}: <absent> -> {right}")),
+                    (None, None) => {}
+                }
+            }
+        }
+        _ if a != b => differences.push(format!("{}: {a} -> {b}", if path.is_empty() { "<root>" } else { path })),
+        _ => {}
+    }
+}
+
+impl Implementation {
+    /// Returns the label to show to users: the `title` when present, otherwise the `name`.
+    pub fn display_name(&self) -> &str {
+        self.title.as_deref().unwrap_or(&self.name)
+    }
+}
+
+impl InitializeResult {
+    /// Returns the protocol version the server chose for the session.
+    pub fn negotiated_version(&self) -> &str {
+        &self.protocol_version
+    }
+
+    /// Returns `true` if the server accepted the protocol version requested by the client.
+    ///
+    /// A `false` result means the server answered with a different version it supports; the
+    /// client should either continue with that version or disconnect.
+    pub fn version_matches(&self, requested: &str) -> bool {
+        self.protocol_version == requested
+    }
+
+    /// Sets the instructions describing how to use the server and its features.
+    pub fn with_instructions(mut self, instructions: impl Into<String>) -> Self {
+        self.instructions = Some(instructions.into());
+        self
+    }
+
+    /// Returns the instructions limited to their first `max_chars` characters, for display in limited UI space.
+    pub fn instructions_truncated(&self, max_chars: usize) -> Option<String> {
+        self.instructions
+            .as_deref()
+            .map(|instructions| instructions.chars().take(max_chars).collect())
+    }
+}
+
+impl PromptMessage {
+    /// Returns the text of the message if its content is `TextContent`.
+    pub fn as_text(&self) -> Option<&str> {
+        match &self.content {
+            ContentBlock::TextContent(text_content) => Some(&text_content.text),
+            _ => None,
+        }
+    }
+}
+
+impl GetPromptResult {
+    /// Renders the text messages of the prompt as `role: text` lines, for previews and debugging.
+    /// Messages without text content are skipped.
+    pub fn render_text(&self) -> String {
+        self.messages
+            .iter()
+            .filter_map(|message| message.as_text().map(|text| format!("{}: {text}", message.role)))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+impl ProgressNotification {
+    /// Creates a progress update for the request identified by `progress_token`,
+    /// optionally annotated with a human-readable `message`.
+    pub fn for_token(progress_token: ProgressToken, progress: f64, total: Option<f64>, message: Option<String>) -> Self {
+        Self::new(ProgressNotificationParams {
+            message,
+            meta: None,
+            progress,
+            progress_token,
+            total,
+        })
+    }
+
+    /// Sets the human-readable message describing the current progress.
+    pub fn with_message(mut self, message: String) -> Self {
+        self.params.message = Some(message);
+        self
+    }
+}
+
+impl LoggingMessageNotification {
+    /// Creates a log notification whose `data` is the JSON serialization of a structured record.
+    ///
+    /// # Errors
+    /// Returns an `internal_error` `RpcError` if `data` cannot be serialized.
+    pub fn structured<T: serde::Serialize>(
+        level: LoggingLevel,
+        logger: Option<String>,
+        data: &T,
+    ) -> std::result::Result<Self, RpcError> {
+        let data = serde_json::to_value(data)
+            .map_err(|error| RpcError::internal_error().with_data(Some(json!({ "details" : error.to_string() }))))?;
+        Ok(Self::new(LoggingMessageNotificationParams {
+            data,
+            level,
+            logger,
+            meta: None,
+        }))
+    }
+}
+
+/// Returns `true` if the MIME type denotes textual content.
+///
+/// This is a heuristic: `text/*`, `+json`/`+xml` suffixed types and a few well known textual
+/// `application/*` types are considered textual, as is any type declaring a `charset` parameter.
+fn is_textual_mime_type(mime_type: &str) -> bool {
+    let mime_type = mime_type.to_ascii_lowercase();
+    let mut parts = mime_type.split(';');
+    let essence = parts.next().unwrap_or_default().trim();
+    if parts.any(|parameter| parameter.trim().starts_with("charset=")) {
+        return true;
+    }
+    essence.starts_with("text/")
+        || essence.ends_with("+json")
+        || essence.ends_with("+xml")
+        || matches!(
+            essence,
+            "application/json"
+                | "application/xml"
+                | "application/javascript"
+                | "application/ecmascript"
+                | "application/yaml"
+                | "application/x-yaml"
+                | "application/toml"
+                | "application/sql"
+                | "application/graphql"
+                | "application/x-sh"
+        )
+}
+
+impl EmbeddedResource {
+    /// Checks that the declared MIME type of the embedded resource is consistent with its content variant.
+    ///
+    /// Text contents must declare a textual MIME type (e.g. not `application/octet-stream`),
+    /// and blob contents must not declare a `text/*` MIME type.
+    /// Resources without a MIME type are always considered consistent.
+    ///
+    /// # Errors
+    /// Returns an `invalid_params` error describing the mismatch.
+    pub fn validate(&self) -> std::result::Result<(), RpcError> {
+        match &self.resource {
+            EmbeddedResourceResource::TextResourceContents(contents) => match contents.mime_type.as_deref() {
+                Some(mime_type) if !is_textual_mime_type(mime_type) => {
+                    Err(RpcError::invalid_params().with_message(format!(
+                        "Embedded resource '{}' has text contents but a binary MIME type '{mime_type}'",
+                        contents.uri
+                    )))
+                }
+                _ => Ok(()),
+            },
+            EmbeddedResourceResource::BlobResourceContents(contents) => match contents.mime_type.as_deref() {
+                Some(mime_type) if mime_type.trim().to_ascii_lowercase().starts_with("text/") => {
+                    Err(RpcError::invalid_params().with_message(format!(
+                        "Embedded resource '{}' has blob contents but a text MIME type '{mime_type}'",
+                        contents.uri
+                    )))
+                }
+                _ => Ok(()),
+            },
+        }
+    }
+}
+
+/// Percent-encodes a filesystem path for use in a `file://` URI, leaving unreserved characters, `/` and `:` as is.
+fn percent_encode_path(path: &str) -> String {
+    let mut encoded = String::with_capacity(path.len());
+    for byte in path.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' | b'/' | b':' => encoded.push(byte as char),
+            _ => encoded.push_str(&format!("%{byte:02X}")),
+        }
+    }
+    encoded
+}
+
+/// Decodes percent-encoded sequences, returning `None` for malformed sequences or invalid UTF-8.
+fn percent_decode(input: &str) -> Option<String> {
+    let bytes = input.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+    while index < bytes.len() {
+        if bytes[index] == b'%' {
+            let hex = input.get(index + 1..index + 3)?;
+            if !hex.bytes().all(|byte| byte.is_ascii_hexdigit()) {
+                return None;
+            }
+            decoded.push(u8::from_str_radix(hex, 16).ok()?);
+            index += 3;
+        } else {
+            decoded.push(bytes[index]);
+            index += 1;
+        }
+    }
+    String::from_utf8(decoded).ok()
+}
+
+impl Root {
+    /// Creates a `Root` for an absolute filesystem path, encoding it as a `file://` URI.
+    ///
+    /// # Errors
+    /// Returns an `invalid_params` error if the path is relative or is not valid UTF-8.
+    pub fn from_path(path: &std::path::Path, name: Option<String>) -> std::result::Result<Root, RpcError> {
+        if !path.is_absolute() {
+            return Err(RpcError::invalid_params().with_message(format!("Root path '{}' is not absolute", path.display())));
+        }
+        let path_str = path.to_str().ok_or_else(|| {
+            RpcError::invalid_params().with_message(format!("Root path '{}' is not valid UTF-8", path.display()))
+        })?;
+        // Windows paths use `\` separators and start with a drive letter, e.g. `file:///C:/Users`
+        let path_str = if cfg!(windows) {
+            path_str.replace('\\', "/")
+        } else {
+            path_str.to_string()
+        };
+        let separator = if path_str.starts_with('/') { "" } else { "/" };
+        Ok(Root {
+            meta: None,
+            name,
+            uri: format!("file://{separator}{}", percent_encode_path(&path_str)),
+        })
+    }
+
+    /// Returns the filesystem path of a `file://` root.
+    ///
+    /// Returns `None` for other URI schemes, remote hosts or malformed percent-encoding.
+    pub fn as_path(&self) -> Option<std::path::PathBuf> {
+        let rest = self.uri.strip_prefix("file://")?;
+        let rest = rest.strip_prefix("localhost").unwrap_or(rest);
+        if !rest.starts_with('/') {
+            return None;
+        }
+        let path = percent_decode(rest)?;
+        let path = match path.as_bytes() {
+            [b'/', drive, b':', ..] if cfg!(windows) && drive.is_ascii_alphabetic() => path[1..].to_string(),
+            _ => path,
+        };
+        Some(std::path::PathBuf::from(path))
+    }
+}
+
+impl CreateMessageResult {
+    /// Creates a `CreateMessageResult` answering a sampling request with an assistant text message.
+    ///
+    /// # Arguments
+    /// * `model` - The name of the model that generated the message.
+    /// * `text` - The generated text.
+    /// * `stop_reason` - The reason why sampling stopped, if known (e.g. `"endTurn"`).
+    pub fn assistant_text<M: Into<String>, T: Into<String>>(model: M, text: T, stop_reason: Option<String>) -> Self {
+        Self {
+            content: TextContent::from(text.into()).into(),
+            meta: None,
+            model: model.into(),
+            role: Role::Assistant,
+            stop_reason,
+        }
+    }
+}
+
+/// Builds [`ToolAnnotations`] for attaching to a [`Tool`].
+///
+/// Only the hints that are set are serialized; clients apply the spec defaults for the rest.
+#[derive(Debug, Clone, Default)]
+pub struct ToolAnnotationsBuilder {
+    annotations: ToolAnnotations,
+}
+
+impl ToolAnnotationsBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets a human-readable title for the tool.
+    pub fn title(mut self, title: impl Into<String>) -> Self {
+        self.annotations.title = Some(title.into());
+        self
+    }
+
+    /// Sets `readOnlyHint`: the tool does not modify its environment.
+    pub fn read_only(mut self, read_only: bool) -> Self {
+        self.annotations.read_only_hint = Some(read_only);
+        self
+    }
+
+    /// Sets `destructiveHint`: the tool may perform destructive updates.
+    pub fn destructive(mut self, destructive: bool) -> Self {
+        self.annotations.destructive_hint = Some(destructive);
+        self
+    }
+
+    /// Sets `idempotentHint`: repeated calls with the same arguments have no additional effect.
+    pub fn idempotent(mut self, idempotent: bool) -> Self {
+        self.annotations.idempotent_hint = Some(idempotent);
+        self
+    }
+
+    /// Sets `openWorldHint`: the tool interacts with external entities.
+    pub fn open_world(mut self, open_world: bool) -> Self {
+        self.annotations.open_world_hint = Some(open_world);
+        self
+    }
+
+    pub fn build(self) -> ToolAnnotations {
+        self.annotations
+    }
+}
+
+impl ToolAnnotations {
+    /// Returns a [`ToolAnnotationsBuilder`] with no hints set.
+    pub fn builder() -> ToolAnnotationsBuilder {
+        ToolAnnotationsBuilder::new()
+    }
+}
+
+/// Uniform `_meta` assignment for result types.
+pub trait WithMeta: Sized {
+    /// Replaces the `_meta` object of the result.
+    fn with_meta(self, meta: Option<serde_json::Map<String, Value>>) -> Self;
+}
+
+macro_rules! impl_with_meta {
+    ($($result:ty),* $(,)?) => {
+        $(
+            impl WithMeta for $result {
+                fn with_meta(mut self, meta: Option<serde_json::Map<String, Value>>) -> Self {
+                    self.meta = meta;
+                    self
+                }
+            }
+        )*
+    };
+}
+
+impl_with_meta!(
+    CallToolResult,
+    CancelTaskResult,
+    CompleteResult,
+    CreateMessageResult,
+    CreateTaskResult,
+    ElicitResult,
+    GetPromptResult,
+    GetTaskPayloadResult,
+    GetTaskResult,
+    InitializeResult,
+    ListPromptsResult,
+    ListResourceTemplatesResult,
+    ListResourcesResult,
+    ListRootsResult,
+    ListTasksResult,
+    ListToolsResult,
+    ReadResourceResult,
+    Result,
+);
+
+/// Well-known values of `CreateMessageResult::stop_reason`.
+///
+/// The field is an open string, so providers may report other reasons; those are only available
+/// through the raw `stop_reason` field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum StopReason {
+    /// `endTurn`: the model finished its turn.
+    EndTurn,
+    /// `stopSequence`: a stop sequence was encountered.
+    StopSequence,
+    /// `maxTokens`: the maximum token limit was reached.
+    MaxTokens,
+    /// `toolUse`: the model wants to use one or more tools.
+    ToolUse,
+}
+
+impl StopReason {
+    /// Returns the wire value of the stop reason, e.g. `endTurn`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            StopReason::EndTurn => "endTurn",
+            StopReason::StopSequence => "stopSequence",
+            StopReason::MaxTokens => "maxTokens",
+            StopReason::ToolUse => "toolUse",
+        }
+    }
+}
+
+impl Display for StopReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[derive(Debug)]
+pub struct StopReasonError {
+    invalid_value: String,
+}
+
+impl core::fmt::Display for StopReasonError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "Unknown stop reason: '{}'", self.invalid_value)
+    }
+}
+
+impl std::error::Error for StopReasonError {}
+
+impl FromStr for StopReason {
+    type Err = StopReasonError;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "endTurn" => Ok(Self::EndTurn),
+            "stopSequence" => Ok(Self::StopSequence),
+            "maxTokens" => Ok(Self::MaxTokens),
+            "toolUse" => Ok(Self::ToolUse),
+            _ => Err(StopReasonError {
+                invalid_value: s.to_string(),
+            }),
+        }
+    }
+}
+
+impl CreateMessageResult {
+    /// Returns the typed stop reason, or `None` if it is absent or a provider-specific value.
+    pub fn stop_reason_kind(&self) -> Option<StopReason> {
+        self.stop_reason.as_deref()?.parse().ok()
+    }
+}
+
+impl CompleteRequestRef {
+    /// Builds a reference to the prompt identified by `name`.
+    pub fn prompt(name: impl Into<String>) -> Self {
+        PromptReference::new(name.into(), None).into()
+    }
+
+    /// Builds a reference to the resource or resource template identified by `uri`.
+    pub fn resource(uri: impl Into<String>) -> Self {
+        ResourceTemplateReference::new(uri.into()).into()
+    }
+}
+
+impl CompleteRequest {
+    /// Returns the prompt or resource the completion is requested for.
+    pub fn reference(&self) -> &CompleteRequestRef {
+        &self.params.ref_
+    }
+}
+
+impl CompleteResult {
+    /// Maximum number of completion values allowed in a single response.
+    pub const MAX_VALUES: usize = 100;
+
+    /// Creates a `CompleteResult` from all available completion values.
+    /// Values are truncated to the first [`Self::MAX_VALUES`] items, `total` is set to the
+    /// full number of values and `hasMore` indicates whether any values were left out.
+    pub fn from_all(mut values: Vec<String>) -> Self {
+        let total = values.len();
+        values.truncate(Self::MAX_VALUES);
+        Self {
+            completion: CompleteResultCompletion {
+                has_more: Some(total > Self::MAX_VALUES),
+                total: Some(total as i64),
+                values,
+            },
+            meta: None,
+        }
+    }
+}
+
+impl Result {
+    /// Creates a `Result` carrying custom attributes that are flattened into the serialized result object.
+    pub fn with_extra(extra: serde_json::Map<String, Value>) -> Self {
+        Self {
+            meta: None,
+            extra: Some(extra),
+        }
+    }
+}
+
+pub type CustomNotification = CustomRequest;
+
+/// Converts an `SdkError` into a `RpcError`, preserving its code, message and data,
+/// so transport-level failures can be reported back to the peer as a JSON-RPC error.
+impl From<SdkError> for RpcError {
+    fn from(value: SdkError) -> Self {
+        Self {
+            code: value.code,
+            data: value.data,
+            message: value.message,
+        }
+    }
+}
+
+impl TryFrom<ServerResult> for InitializeResult {
+    type Error = RpcError;
+    fn try_from(value: ServerResult) -> std::result::Result<Self, Self::Error> {
+        if let ServerResult::InitializeResult(result) = value {
+            Ok(result)
+        } else {
+            Err(RpcError::internal_error().with_message("Not a InitializeResult".to_string()))
+        }
+    }
+}
+impl TryFrom<ServerResult> for ListResourcesResult {
+    type Error = RpcError;
+    fn try_from(value: ServerResult) -> std::result::Result<Self, Self::Error> {
+        if let ServerResult::ListResourcesResult(result) = value {
+            Ok(result)
+        } else {
+            Err(RpcError::internal_error().with_message("Not a ListResourcesResult".to_string()))
+        }
+    }
+}
+impl TryFrom<ServerResult> for ListResourceTemplatesResult {
+    type Error = RpcError;
+    fn try_from(value: ServerResult) -> std::result::Result<Self, Self::Error> {
+        if let ServerResult::ListResourceTemplatesResult(result) = value {
+            Ok(result)
+        } else {
+            Err(RpcError::internal_error().with_message("Not a ListResourceTemplatesResult".to_string()))
+        }
+    }
+}
+impl TryFrom<ServerResult> for ReadResourceResult {
+    type Error = RpcError;
+    fn try_from(value: ServerResult) -> std::result::Result<Self, Self::Error> {
+        if let ServerResult::ReadResourceResult(result) = value {
+            Ok(result)
+        } else {
+            Err(RpcError::internal_error().with_message("Not a ReadResourceResult".to_string()))
+        }
+    }
+}
+impl TryFrom<ServerResult> for ListPromptsResult {
+    type Error = RpcError;
+    fn try_from(value: ServerResult) -> std::result::Result<Self, Self::Error> {
+        if let ServerResult::ListPromptsResult(result) = value {
+            Ok(result)
+        } else {
+            Err(RpcError::internal_error().with_message("Not a ListPromptsResult".to_string()))
+        }
+    }
+}
+impl TryFrom<ServerResult> for GetPromptResult {
+    type Error = RpcError;
+    fn try_from(value: ServerResult) -> std::result::Result<Self, Self::Error> {
+        if let ServerResult::GetPromptResult(result) = value {
+            Ok(result)
+        } else {
+            Err(RpcError::internal_error().with_message("Not a GetPromptResult".to_string()))
+        }
+    }
+}
+impl TryFrom<ServerResult> for ListToolsResult {
+    type Error = RpcError;
+    fn try_from(value: ServerResult) -> std::result::Result<Self, Self::Error> {
+        if let ServerResult::ListToolsResult(result) = value {
+            Ok(result)
+        } else {
+            Err(RpcError::internal_error().with_message("Not a ListToolsResult".to_string()))
+        }
+    }
+}
+impl TryFrom<ServerResult> for CallToolResult {
+    type Error = RpcError;
+    fn try_from(value: ServerResult) -> std::result::Result<Self, Self::Error> {
+        if let ServerResult::CallToolResult(result) = value {
+            Ok(result)
+        } else {
+            Err(RpcError::internal_error().with_message("Not a CallToolResult".to_string()))
+        }
+    }
+}
+impl TryFrom<ServerResult> for GetTaskResult {
+    type Error = RpcError;
+    fn try_from(value: ServerResult) -> std::result::Result<Self, Self::Error> {
+        if let ServerResult::GetTaskResult(result) = value {
+            Ok(result)
+        } else {
+            Err(RpcError::internal_error().with_message("Not a GetTaskResult".to_string()))
+        }
+    }
+}
+impl TryFrom<ServerResult> for CancelTaskResult {
+    type Error = RpcError;
+    fn try_from(value: ServerResult) -> std::result::Result<Self, Self::Error> {
+        if let ServerResult::CancelTaskResult(result) = value {
+            Ok(result)
+        } else {
+            Err(RpcError::internal_error().with_message("Not a CancelTaskResult".to_string()))
+        }
+    }
+}
+impl TryFrom<ServerResult> for ListTasksResult {
+    type Error = RpcError;
+    fn try_from(value: ServerResult) -> std::result::Result<Self, Self::Error> {
+        if let ServerResult::ListTasksResult(result) = value {
+            Ok(result)
+        } else {
+            Err(RpcError::internal_error().with_message("Not a ListTasksResult".to_string()))
+        }
+    }
+}
+impl TryFrom<ServerResult> for CompleteResult {
+    type Error = RpcError;
+    fn try_from(value: ServerResult) -> std::result::Result<Self, Self::Error> {
+        if let ServerResult::CompleteResult(result) = value {
+            Ok(result)
+        } else {
+            Err(RpcError::internal_error().with_message("Not a CompleteResult".to_string()))
+        }
+    }
+}
+impl TryFrom<ServerResult> for Result {
+    type Error = RpcError;
+    fn try_from(value: ServerResult) -> std::result::Result<Self, Self::Error> {
+        if let ServerResult::Result(result) = value {
+            Ok(result)
+        } else {
+            Err(RpcError::internal_error().with_message("Not a Result".to_string()))
+        }
+    }
+}
+impl TryFrom<ServerResult> for GetTaskPayloadResult {
+    type Error = RpcError;
+    fn try_from(value: ServerResult) -> std::result::Result<Self, Self::Error> {
+        if let ServerResult::GetTaskPayloadResult(result) = value {
+            Ok(result)
+        } else {
+            Err(RpcError::internal_error().with_message("Not a GetTaskPayloadResult".to_string()))
+        }
+    }
+}
+
+/// Known error code categories, unifying the standard JSON-RPC, MCP and SDK error codes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ErrorCodeKind {
+    /// `-32700`: invalid JSON was received.
+    ParseError,
+    /// `-32600`: the JSON sent is not a valid request object.
+    InvalidRequest,
+    /// `-32601`: the method does not exist or is not available.
+    MethodNotFound,
+    /// `-32602`: invalid method parameters.
+    InvalidParams,
+    /// `-32603`: internal JSON-RPC error.
+    InternalError,
+    /// `-32042`: a required URL elicitation was not completed.
+    UrlElicitationRequired,
+    /// `-32000`: the connection was closed.
+    ConnectionClosed,
+    /// `-32001`: the request timed out.
+    RequestTimeout,
+    /// `-32002`: the requested resource was not found.
+    ResourceNotFound,
+    /// `-32015`: the request was malformed.
+    BadRequest,
+    /// `-32016`: the session was not found.
+    SessionNotFound,
+}
+
+impl RpcError {
+    /// Maps the numeric `code` to a known [`ErrorCodeKind`].
+    ///
+    /// Returns `None` for application-defined error codes.
+    pub fn code_kind(&self) -> Option<ErrorCodeKind> {
+        let kinds = [
+            (i64::from(RpcErrorCodes::PARSE_ERROR), ErrorCodeKind::ParseError),
+            (i64::from(RpcErrorCodes::INVALID_REQUEST), ErrorCodeKind::InvalidRequest),
+            (i64::from(RpcErrorCodes::METHOD_NOT_FOUND), ErrorCodeKind::MethodNotFound),
+            (i64::from(RpcErrorCodes::INVALID_PARAMS), ErrorCodeKind::InvalidParams),
+            (i64::from(RpcErrorCodes::INTERNAL_ERROR), ErrorCodeKind::InternalError),
+            (
+                i64::from(RpcErrorCodes::URL_ELICITATION_REQUIRED),
+                ErrorCodeKind::UrlElicitationRequired,
+            ),
+            (i64::from(SdkErrorCodes::CONNECTION_CLOSED), ErrorCodeKind::ConnectionClosed),
+            (i64::from(SdkErrorCodes::REQUEST_TIMEOUT), ErrorCodeKind::RequestTimeout),
+            (i64::from(SdkErrorCodes::RESOURCE_NOT_FOUND), ErrorCodeKind::ResourceNotFound),
+            (i64::from(SdkErrorCodes::BAD_REQUEST), ErrorCodeKind::BadRequest),
+            (i64::from(SdkErrorCodes::SESSION_NOT_FOUND), ErrorCodeKind::SessionNotFound),
+        ];
+        kinds.into_iter().find_map(|(code, kind)| (code == self.code).then_some(kind))
+    }
+
+    /// Returns `true` if the failed request may succeed when sent again.
+    ///
+    /// Connection, timeout and internal errors are considered transient. Malformed requests,
+    /// unknown methods, invalid params and application-defined codes are not.
+    pub fn is_retryable(&self) -> bool {
+        matches!(
+            self.code_kind(),
+            Some(ErrorCodeKind::ConnectionClosed | ErrorCodeKind::RequestTimeout | ErrorCodeKind::InternalError)
+        )
+    }
+
+    /// Creates an application-defined error with the given `code` and `message`.
+    ///
+    /// # Errors
+    /// Returns an `invalid_params` `RpcError` if `code` falls within the range reserved for
+    /// JSON-RPC and MCP protocol errors (`-32768..=-32000`).
+    ///
+    /// # Example
+    /// ```
+    /// use rust_mcp_schema::RpcError;
+    ///
+    /// let error = RpcError::application_error(1001, "Quota exceeded").unwrap();
+    /// assert_eq!(error.code, 1001);
+    /// assert!(RpcError::application_error(-32601, "Not found").is_err());
+    /// ```
+    pub fn application_error(code: i64, message: impl Into<String>) -> std::result::Result<Self, RpcError> {
+        if (-32768..=-32000).contains(&code) {
+            return Err(RpcError::invalid_params()
+                .with_message(format!("Error code {code} is within the reserved range -32768..=-32000")));
+        }
+        Ok(Self {
+            code,
+            data: None,
+            message: message.into(),
+        })
+    }
+
+    /// Attaches a stable localization key under `data._i18n`, keeping the English `message` as is,
+    /// so clients can look up a translated message for display.
+    ///
+    /// Object `data` is extended in place; any other existing `data` value is moved under `data.details`.
+    pub fn with_localization_key(mut self, key: &str) -> Self {
+        let mut data = match self.data.take() {
+            Some(Value::Object(map)) => map,
+            Some(other) => serde_json::Map::from_iter([("details".to_string(), other)]),
+            None => serde_json::Map::new(),
+        };
+        data.insert("_i18n".to_string(), Value::String(key.to_string()));
+        self.data = Some(Value::Object(data));
+        self
+    }
+
+    /// Returns the localization key set by [`RpcError::with_localization_key`], if any.
+    pub fn localization_key(&self) -> Option<&str> {
+        self.data.as_ref()?.get("_i18n")?.as_str()
+    }
+}
+
+impl JsonrpcErrorResponse {
+    /// Returns the error object carried by this response.
+    pub fn error(&self) -> &RpcError {
+        &self.error
+    }
+}
+
+/// Converts a `serde_json::Error` into a `parse_error` `RpcError` carrying the serde message in
+/// `data.details`, so `?` can be used on serde calls in functions returning `RpcError`.
+impl From<serde_json::Error> for RpcError {
+    fn from(error: serde_json::Error) -> Self {
+        RpcError::parse_error().with_data(Some(json!({ "details" : error.to_string() })))
+    }
+}
+
+/// Two errors are equal when their code, message and data are equal.
+impl PartialEq for RpcError {
+    fn eq(&self, other: &Self) -> bool {
+        self.code == other.code && self.message == other.message && self.data == other.data
+    }
+}
+
+impl Eq for RpcError {}
+
+impl Hash for RpcError {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.code.hash(state);
+        self.message.hash(state);
+        match &self.data {
+            Some(data) => hash_canonical_json(data, state),
+            None => state.write_u8(0xff),
+        }
+    }
+}
+
+/// The variant of a [`ServerResult`], without its payload.
+///
+/// Allows exhaustive dispatch with `match result.kind()` before downcasting with the `TryFrom` impls.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ServerResultKind {
+    InitializeResult,
+    ListResourcesResult,
+    ListResourceTemplatesResult,
+    ReadResourceResult,
+    ListPromptsResult,
+    GetPromptResult,
+    ListToolsResult,
+    CallToolResult,
+    GetTaskResult,
+    CancelTaskResult,
+    ListTasksResult,
+    CompleteResult,
+    Result,
+    GetTaskPayloadResult,
+}
+
+impl ServerResult {
+    /// Returns the kind of this result.
+    pub fn kind(&self) -> ServerResultKind {
+        match self {
+            ServerResult::InitializeResult(_) => ServerResultKind::InitializeResult,
+            ServerResult::ListResourcesResult(_) => ServerResultKind::ListResourcesResult,
+            ServerResult::ListResourceTemplatesResult(_) => ServerResultKind::ListResourceTemplatesResult,
+            ServerResult::ReadResourceResult(_) => ServerResultKind::ReadResourceResult,
+            ServerResult::ListPromptsResult(_) => ServerResultKind::ListPromptsResult,
+            ServerResult::GetPromptResult(_) => ServerResultKind::GetPromptResult,
+            ServerResult::ListToolsResult(_) => ServerResultKind::ListToolsResult,
+            ServerResult::CallToolResult(_) => ServerResultKind::CallToolResult,
+            ServerResult::GetTaskResult(_) => ServerResultKind::GetTaskResult,
+            ServerResult::CancelTaskResult(_) => ServerResultKind::CancelTaskResult,
+            ServerResult::ListTasksResult(_) => ServerResultKind::ListTasksResult,
+            ServerResult::CompleteResult(_) => ServerResultKind::CompleteResult,
+            ServerResult::Result(_) => ServerResultKind::Result,
+            ServerResult::GetTaskPayloadResult(_) => ServerResultKind::GetTaskPayloadResult,
+        }
+    }
+}
 
 /// BEGIN AUTO GENERATED
 impl ::serde::Serialize for ServerJsonrpcResponse {
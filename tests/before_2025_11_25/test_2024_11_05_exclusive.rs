@@ -83,3 +83,320 @@ fn test_client_list_resource_templates_request_sample() {
             if matches!(client_request, ClientRequest::ListResourceTemplatesRequest(_)))
     ));
 }
+
+#[test]
+fn test_sdk_error_request_timeout() {
+    let error = SdkError::request_timeout(5000);
+    assert_eq!(error.code, i64::from(SdkErrorCodes::REQUEST_TIMEOUT));
+    assert_eq!(error.code, -32001);
+    assert_eq!(error.message, "Request timeout");
+    assert_eq!(error.data, Some(serde_json::json!({ "timeout": 5000 })));
+}
+
+#[test]
+fn test_sdk_error_into_rpc_error() {
+    let rpc_error: RpcError = SdkError::session_not_found().into();
+    assert_eq!(rpc_error.code, i64::from(SdkErrorCodes::SESSION_NOT_FOUND));
+    assert_eq!(rpc_error.message, "Session not found");
+    assert!(rpc_error.data.is_none());
+
+    let rpc_error: RpcError = SdkError::request_timeout(100).into();
+    assert_eq!(rpc_error.code, -32001);
+    assert_eq!(rpc_error.data, Some(serde_json::json!({ "timeout": 100 })));
+}
+
+#[test]
+fn test_jsonrpc_id_and_method_accessors() {
+    let request = ClientJsonrpcRequest::new(
+        RequestId::Integer(3),
+        RequestFromClient::ClientRequest(ClientRequest::PingRequest(PingRequest::new(None))),
+    );
+    assert_eq!(request.id(), &RequestId::Integer(3));
+    assert_eq!(request.clone_id(), RequestId::Integer(3));
+    assert_eq!(request.method(), "ping");
+
+    let request = ServerJsonrpcRequest::new(
+        RequestId::String("abc".to_string()),
+        RequestFromServer::ServerRequest(ServerRequest::PingRequest(PingRequest::new(None))),
+    );
+    assert_eq!(request.id(), &RequestId::String("abc".to_string()));
+    assert_eq!(request.clone_id(), RequestId::String("abc".to_string()));
+    assert_eq!(request.method(), "ping");
+
+    let response = ServerJsonrpcResponse::new(
+        RequestId::Integer(4),
+        ResultFromServer::ServerResult(ServerResult::Result(Result::with_extra(serde_json::Map::new()))),
+    );
+    assert_eq!(response.id(), &RequestId::Integer(4));
+    assert_eq!(response.clone_id(), RequestId::Integer(4));
+
+    let response = ClientJsonrpcResponse::new(
+        RequestId::Integer(5),
+        ResultFromClient::ClientResult(ClientResult::Result(Result::with_extra(serde_json::Map::new()))),
+    );
+    assert_eq!(response.id(), &RequestId::Integer(5));
+    assert_eq!(response.clone_id(), RequestId::Integer(5));
+}
+
+#[test]
+fn test_params_value() {
+    let message = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "tools/call",
+        "params": { "name": "add_numbers", "arguments": { "a": 1, "b": 2 } }
+    });
+    let request: ClientJsonrpcRequest = serde_json::from_value(message.clone()).unwrap();
+    assert_eq!(request.params_value(), message["params"]);
+
+    let message = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 2,
+        "method": "custom/method",
+        "params": { "key": "value" }
+    });
+    let request: ClientJsonrpcRequest = serde_json::from_value(message.clone()).unwrap();
+    assert_eq!(request.params_value(), message["params"]);
+}
+
+#[test]
+fn test_request_with_null_params() {
+    let message = r#"{"jsonrpc":"2.0","id":1,"method":"tools/list","params":null}"#;
+    let request: ClientJsonrpcRequest = serde_json::from_str(message).unwrap();
+    assert!(matches!(
+        request.request,
+        RequestFromClient::ClientRequest(ClientRequest::ListToolsRequest(_))
+    ));
+
+    let message = r#"{"jsonrpc":"2.0","method":"notifications/initialized","params":null}"#;
+    let notification: ClientJsonrpcNotification = serde_json::from_str(message).unwrap();
+    assert!(matches!(
+        notification.notification,
+        NotificationFromClient::ClientNotification(ClientNotification::InitializedNotification(_))
+    ));
+
+    // null and absent params are handled alike, without substituting a value the peer did not send
+    let with_null: ClientJsonrpcRequest =
+        serde_json::from_str(r#"{"jsonrpc":"2.0","id":2,"method":"acme/run","params":null}"#).unwrap();
+    let without: ClientJsonrpcRequest = serde_json::from_str(r#"{"jsonrpc":"2.0","id":2,"method":"acme/run"}"#).unwrap();
+    assert_eq!(
+        serde_json::to_value(&with_null).unwrap(),
+        serde_json::to_value(&without).unwrap()
+    );
+    assert!(with_null.params_value().is_null());
+}
+
+#[test]
+fn test_optional_params_requests_default() {
+    assert_eq!(
+        serde_json::to_value(ListToolsRequest::default()).unwrap(),
+        serde_json::to_value(ListToolsRequest::new(None)).unwrap()
+    );
+    assert_eq!(
+        serde_json::to_value(PingRequest::default()).unwrap(),
+        serde_json::json!({"method": "ping"})
+    );
+}
+
+#[test]
+fn test_notification_with_id_is_rejected() {
+    use std::str::FromStr;
+
+    let message = r#"{"jsonrpc":"2.0","id":1,"method":"notifications/initialized"}"#;
+    let error = ClientJsonrpcNotification::from_str(message).unwrap_err();
+    assert_eq!(error.code, RpcError::invalid_request().code);
+    assert_eq!(error.message, "notifications must not carry an id");
+
+    let error = serde_json::from_str::<ClientJsonrpcNotification>(message).unwrap_err();
+    assert!(error.to_string().contains("notifications must not carry an id"));
+}
+
+#[test]
+fn test_request_display() {
+    assert_eq!(ClientRequest::PingRequest(PingRequest::new(None)).to_string(), "ping");
+}
+
+#[test]
+fn test_empty_result_is_standard_result() {
+    let message: ServerMessage = r#"{"jsonrpc":"2.0","id":1,"result":{}}"#.parse().unwrap();
+    let ServerMessage::Response(response) = message else {
+        panic!("Expected a response");
+    };
+    assert!(matches!(
+        response.result,
+        ResultFromServer::ServerResult(ServerResult::Result(_))
+    ));
+
+    let message: ServerMessage = r#"{"jsonrpc":"2.0","id":2,"result":{"_meta":{"progressToken":1}}}"#.parse().unwrap();
+    let ServerMessage::Response(response) = message else {
+        panic!("Expected a response");
+    };
+    assert!(matches!(
+        response.result,
+        ResultFromServer::ServerResult(ServerResult::Result(Result { meta: Some(_), .. }))
+    ));
+
+    // a ping request is answered with an empty result
+    let request = ClientJsonrpcRequest::new(
+        RequestId::Integer(1),
+        ClientRequest::PingRequest(PingRequest::new(None)).into(),
+    );
+    let message: ServerMessage = r#"{"jsonrpc":"2.0","id":1,"result":{}}"#.parse().unwrap();
+    assert!(message.is_response_to(&request));
+
+    // values that fit no standard result are kept as custom results
+    let message: ServerMessage = r#"{"jsonrpc":"2.0","id":3,"result":"done"}"#.parse().unwrap();
+    let ServerMessage::Response(response) = message else {
+        panic!("Expected a response");
+    };
+    assert!(matches!(response.result, ResultFromServer::CustomResult(_)));
+}
+
+#[test]
+fn test_tool_and_prompt_content_conversion() {
+    let content = CallToolResultContentItem::text_content("hello".to_string(), None);
+    let prompt_content: PromptMessageContent = content.into();
+    assert!(matches!(&prompt_content, PromptMessageContent::TextContent(text) if text.text == "hello"));
+    let content: CallToolResultContentItem = prompt_content.into();
+    assert!(matches!(content, CallToolResultContentItem::TextContent(text) if text.text == "hello"));
+
+    let content = CallToolResultContentItem::image_content("aGVsbG8=".to_string(), "image/png".to_string(), None);
+    let prompt_content: PromptMessageContent = content.into();
+    assert!(
+        matches!(&prompt_content, PromptMessageContent::ImageContent(image) if image.mime_type == "image/png" && image.data == "aGVsbG8=")
+    );
+    let content: CallToolResultContentItem = prompt_content.into();
+    assert!(matches!(content, CallToolResultContentItem::ImageContent(image) if image.mime_type == "image/png"));
+}
+
+#[test]
+fn test_request_from_method_and_params() {
+    let request = RequestFromClient::from_method_and_params("tools/call", serde_json::json!({ "name": "add" })).unwrap();
+    assert!(request.is_for_tool("add"));
+
+    let error = RequestFromClient::from_method_and_params("resources/read", serde_json::json!({})).unwrap_err();
+    assert_eq!(error.code, RpcError::invalid_params().code);
+
+    let request = RequestFromClient::from_method_and_params("custom/echo", serde_json::json!({ "text": "hi" })).unwrap();
+    assert!(matches!(&request, RequestFromClient::CustomRequest(custom) if custom["params"]["text"] == "hi"));
+    assert_eq!(request.method(), "custom/echo");
+}
+
+#[test]
+fn test_progress_notification_for_token() {
+    let notification = ProgressNotification::for_token(ProgressToken::Integer(7), 3.0, Some(4.0));
+    let value = serde_json::to_value(&notification).unwrap();
+    assert_eq!(
+        value["params"],
+        serde_json::json!({ "progressToken": 7, "progress": 3.0, "total": 4.0 })
+    );
+}
+
+#[test]
+fn test_result_none_fields_are_not_serialized() {
+    let result = ListToolsResult {
+        meta: None,
+        next_cursor: None,
+        tools: vec![],
+    };
+    let message = ServerMessage::Response(ServerJsonrpcResponse::new(
+        RequestId::Integer(1),
+        ResultFromServer::ServerResult(result.into()),
+    ));
+    let value = serde_json::to_value(&message).unwrap();
+    assert_eq!(value["result"], serde_json::json!({ "tools": [] }));
+}
+
+#[test]
+fn test_custom_request_builder() {
+    let request = ClientJsonrpcRequest::custom(RequestId::Integer(5), "experimental/sum")
+        .param("x", 1)
+        .param("y", 2)
+        .build();
+    assert_eq!(request.method(), "experimental/sum");
+    assert_eq!(request.params_value(), serde_json::json!({"x": 1, "y": 2}));
+    assert!(matches!(request.request, RequestFromClient::CustomRequest(_)));
+
+    let request = ServerJsonrpcRequest::custom(RequestId::Integer(6), "experimental/ping").build();
+    assert_eq!(request.method, "experimental/ping");
+}
+
+#[test]
+fn test_custom_payload_without_method_does_not_panic() {
+    let request = RequestFromServer::CustomRequest(serde_json::json!({ "params": {} }));
+    assert_eq!(request.method(), "");
+    let request = ServerJsonrpcRequest::new(RequestId::Integer(1), request);
+    assert_eq!(request.method, "");
+
+    let notification = NotificationFromServer::CustomNotification(serde_json::json!({ "method": 42 }));
+    assert_eq!(notification.method(), "");
+
+    let request = RequestFromClient::CustomRequest(serde_json::json!({}));
+    assert_eq!(request.method(), "");
+}
+
+#[test]
+fn test_parse_client_message_strict_version() {
+    let message = r#"{"jsonrpc":"2.0","id":1,"method":"initialize","params":{"protocolVersion":"2024-11-05","capabilities":{"roots":{"listChanged":true}},"clientInfo":{"name":"client","version":"1.0.0"}}}"#;
+    assert!(parse_client_message_strict_version(message).unwrap().is_request());
+
+    // `elicitation` capability was introduced in 2025-06-18
+    let message = r#"{"jsonrpc":"2.0","id":1,"method":"initialize","params":{"protocolVersion":"2024-11-05","capabilities":{"elicitation":{}},"clientInfo":{"name":"client","version":"1.0.0"}}}"#;
+    let error = parse_client_message_strict_version(message).unwrap_err();
+    assert_eq!(error.code, RpcError::invalid_request().code);
+    assert!(error.message.contains("params.capabilities.elicitation"));
+
+    // keys inside tool arguments are not schema properties
+    let message =
+        r#"{"jsonrpc":"2.0","id":2,"method":"tools/call","params":{"name":"render","arguments":{"title":"Report"}}}"#;
+    assert!(parse_client_message_strict_version(message).is_ok());
+
+    // custom requests may use any property names
+    let message = r#"{"jsonrpc":"2.0","id":3,"method":"acme/install","params":{"title":"Report","status":"draft"}}"#;
+    assert!(parse_client_message_strict_version(message).is_ok());
+}
+
+#[test]
+fn test_implementation_display_name() {
+    let implementation = Implementation {
+        name: "example-client".to_string(),
+        version: "1.0.0".to_string(),
+    };
+    assert_eq!(implementation.display_name(), "example-client");
+}
+
+#[test]
+fn test_request_from_client_visit() {
+    let method_of = |request: &RequestFromClient| -> String {
+        request.visit(
+            |standard| format!("standard:{}", standard.method()),
+            |custom| format!("custom:{}", custom["method"].as_str().unwrap_or_default()),
+        )
+    };
+
+    let request = RequestFromClient::ClientRequest(ClientRequest::ListToolsRequest(ListToolsRequest::new(None)));
+    assert_eq!(method_of(&request), "standard:tools/list");
+
+    let custom = RequestFromClient::CustomRequest(serde_json::json!({ "method": "acme/reindex" }));
+    assert_eq!(method_of(&custom), "custom:acme/reindex");
+}
+
+#[test]
+fn test_negotiate_capabilities() {
+    let server = ServerCapabilities {
+        tools: Some(ServerCapabilitiesTools { list_changed: None }),
+        experimental: Some([("acme/streaming".to_string(), serde_json::Map::new())].into()),
+        ..Default::default()
+    };
+    let client = ClientCapabilities {
+        sampling: Some(serde_json::Map::new()),
+        experimental: Some([("acme/streaming".to_string(), serde_json::Map::new())].into()),
+        ..Default::default()
+    };
+
+    let negotiated = server.negotiate(&client);
+    assert_eq!(negotiated.experimental, vec!["acme/streaming".to_string()]);
+    assert!(!negotiated.tools);
+    assert!(!negotiated.sampling);
+    assert!(!negotiated.elicitation);
+}
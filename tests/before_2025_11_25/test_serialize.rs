@@ -897,3 +897,51 @@ fn test_server_list_resources_result() {
             if matches!(server_result, ServerResult::ListResourcesResult(_)))
     ));
 }
+
+#[test]
+fn test_client_request_progress_token() {
+    let request: RequestFromClient = ClientRequest::PingRequest(PingRequest::new(None)).into();
+    assert!(request.progress_token().is_none());
+
+    let request = request.with_progress_token(ProgressToken::String("ping-1".to_string()));
+    assert!(matches!(request.progress_token(), Some(ProgressToken::String(token)) if token == "ping-1"));
+    let value = serde_json::to_value(&request).unwrap();
+    assert_eq!(value["params"]["_meta"]["progressToken"], "ping-1");
+
+    let request = RequestFromClient::CustomRequest(json!({"method": "custom/echo", "params": null}))
+        .with_progress_token(ProgressToken::Integer(7));
+    assert!(matches!(request.progress_token(), Some(ProgressToken::Integer(7))));
+    assert!(matches!(&request, RequestFromClient::CustomRequest(custom) if custom["params"]["_meta"]["progressToken"] == 7));
+
+    // the params of other standard requests do not define `_meta` in this schema version
+    let request: RequestFromClient = ClientRequest::ListToolsRequest(ListToolsRequest::new(None)).into();
+    let request = request.with_progress_token(ProgressToken::Integer(1));
+    assert!(request.meta().is_none());
+}
+
+#[test]
+fn test_copy_meta_traceparent() {
+    let traceparent = "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01";
+    let request = RequestFromClient::CustomRequest(json!({
+        "method": "custom/echo",
+        "params": {"_meta": {"traceparent": traceparent}}
+    }));
+
+    let mut result = ResultFromServer::ServerResult(ServerResult::Result(Result {
+        meta: Some(serde_json::Map::from_iter([("traceparent".to_string(), json!("stale"))])),
+        extra: None,
+    }));
+    copy_meta(&request, &mut result);
+    assert_eq!(result.meta().unwrap()["traceparent"], traceparent);
+
+    let mut result = ResultFromServer::CustomResult(json!({"echo": "hi"}));
+    copy_meta(&request, &mut result);
+    let value = serde_json::to_value(&result).unwrap();
+    assert_eq!(value["_meta"]["traceparent"], traceparent);
+    assert_eq!(value["echo"], "hi");
+
+    // a request without `_meta` leaves the result unchanged
+    let mut result = ResultFromServer::CustomResult(json!({"echo": "hi"}));
+    copy_meta(&ClientRequest::PingRequest(PingRequest::new(None)).into(), &mut result);
+    assert!(result.meta().is_none());
+}
@@ -2,3 +2,12 @@
 pub mod common;
 
 mod schema_2025_11_25;
+mod test_capabilities;
+mod test_content;
+mod test_errors;
+mod test_messages;
+mod test_notifications;
+mod test_requests;
+mod test_results;
+mod test_roots;
+mod test_tools;
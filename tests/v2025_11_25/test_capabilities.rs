@@ -0,0 +1,139 @@
+use rust_mcp_schema::schema_utils::*;
+use rust_mcp_schema::*;
+
+#[test]
+fn test_negotiate_server_tools_with_empty_client() {
+    let server = ServerCapabilities {
+        tools: Some(ServerCapabilitiesTools {
+            list_changed: Some(true),
+        }),
+        ..Default::default()
+    };
+    let client = ClientCapabilities::default();
+
+    // the client advertises nothing, so nothing is supported by both sides
+    let negotiated = server.negotiate(&client);
+    assert_eq!(negotiated, NegotiatedCapabilities::default());
+    assert!(!negotiated.tools);
+}
+
+#[test]
+fn test_negotiate_intersects_capabilities() {
+    let experimental = |names: &[&str]| {
+        names
+            .iter()
+            .map(|name| (name.to_string(), serde_json::Map::new()))
+            .collect::<std::collections::BTreeMap<_, _>>()
+    };
+    let server = ServerCapabilities {
+        resources: Some(ServerCapabilitiesResources {
+            list_changed: None,
+            subscribe: Some(true),
+        }),
+        tasks: Some(ServerTasks::default()),
+        experimental: Some(experimental(&["acme/streaming", "acme/batch"])),
+        ..Default::default()
+    };
+    let client = ClientCapabilities {
+        roots: Some(ClientRoots {
+            list_changed: Some(true),
+        }),
+        tasks: Some(ClientTasks::default()),
+        experimental: Some(experimental(&["acme/streaming", "acme/search"])),
+        ..Default::default()
+    };
+
+    let negotiated = server.negotiate(&client);
+    assert!(negotiated.tasks);
+    assert_eq!(negotiated.experimental, vec!["acme/streaming".to_string()]);
+    assert!(!negotiated.resources);
+    assert!(!negotiated.resources_subscribe);
+    assert!(!negotiated.roots);
+}
+
+#[test]
+fn test_initialize_result_version_matching() {
+    let result: InitializeResult = serde_json::from_value(serde_json::json!({
+        "capabilities": {},
+        "protocolVersion": LATEST_PROTOCOL_VERSION,
+        "serverInfo": { "name": "test-server", "version": "1.0.0" }
+    }))
+    .unwrap();
+    assert!(result.version_matches(LATEST_PROTOCOL_VERSION));
+    assert_eq!(result.negotiated_version(), LATEST_PROTOCOL_VERSION);
+
+    let result = InitializeResult {
+        protocol_version: "2025-06-18".to_string(),
+        ..result
+    };
+    assert!(!result.version_matches(LATEST_PROTOCOL_VERSION));
+    assert_eq!(result.negotiated_version(), "2025-06-18");
+}
+
+#[test]
+fn test_initialize_result_instructions_truncated() {
+    let result: InitializeResult = serde_json::from_value(serde_json::json!({
+        "capabilities": {},
+        "protocolVersion": LATEST_PROTOCOL_VERSION,
+        "serverInfo": { "name": "test-server", "version": "1.0.0" }
+    }))
+    .unwrap();
+    assert_eq!(result.instructions_truncated(10), None);
+
+    let result = result.with_instructions("Use the search tool before answering questions about the codebase.");
+    assert_eq!(result.instructions_truncated(14).as_deref(), Some("Use the search"));
+
+    let result = result.with_instructions("Be brief.");
+    assert_eq!(result.instructions_truncated(14).as_deref(), Some("Be brief."));
+}
+
+#[test]
+fn test_capabilities_diff() {
+    let sent = ServerCapabilities {
+        tools: Some(ServerCapabilitiesTools {
+            list_changed: Some(true),
+        }),
+        ..Default::default()
+    };
+    let reported = ServerCapabilities {
+        tools: Some(ServerCapabilitiesTools {
+            list_changed: Some(false),
+        }),
+        ..Default::default()
+    };
+    assert_eq!(capabilities_diff(&sent, &reported), vec!["tools.listChanged: true -> false"]);
+    assert!(capabilities_diff(&sent, &sent.clone()).is_empty());
+
+    let without_tools = ServerCapabilities::default();
+    assert_eq!(
+        capabilities_diff(&sent, &without_tools),
+        vec![r#"tools: {"listChanged":true} -> <absent>"#]
+    );
+
+    let client = ClientCapabilities {
+        roots: Some(ClientRoots {
+            list_changed: Some(true),
+        }),
+        ..Default::default()
+    };
+    assert_eq!(
+        client_capabilities_diff(&ClientCapabilities::default(), &client),
+        vec![r#"roots: <absent> -> {"listChanged":true}"#]
+    );
+}
+
+#[test]
+fn test_implementation_display_name() {
+    let mut implementation = Implementation {
+        name: "example-server".to_string(),
+        version: "1.0.0".to_string(),
+        title: Some("Example Server".to_string()),
+        description: None,
+        icons: vec![],
+        website_url: None,
+    };
+    assert_eq!(implementation.display_name(), "Example Server");
+
+    implementation.title = None;
+    assert_eq!(implementation.display_name(), "example-server");
+}
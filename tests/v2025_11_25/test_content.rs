@@ -0,0 +1,177 @@
+use rust_mcp_schema::*;
+
+#[test]
+fn test_image_content_decode_data() {
+    let image = ImageContent::new("aGVsbG8gd29ybGQ=".to_string(), "image/png".to_string(), None, None);
+    assert_eq!(image.decode_data().unwrap(), b"hello world");
+
+    let image = ImageContent::new("AAEC/w==".to_string(), "image/png".to_string(), None, None);
+    assert_eq!(image.decode_data().unwrap(), vec![0u8, 1, 2, 255]);
+
+    let image = ImageContent::new(String::new(), "image/png".to_string(), None, None);
+    assert!(image.decode_data().unwrap().is_empty());
+}
+
+#[test]
+fn test_audio_content_decode_data() {
+    let audio = AudioContent::new("TWFu".to_string(), "audio/wav".to_string(), None, None);
+    assert_eq!(audio.decode_data().unwrap(), b"Man");
+
+    // unpadded input is accepted
+    let audio = AudioContent::new("TWE".to_string(), "audio/wav".to_string(), None, None);
+    assert_eq!(audio.decode_data().unwrap(), b"Ma");
+}
+
+#[test]
+fn test_decode_data_invalid_base64() {
+    for data in ["not base64!", "TWFuT", "TW=u", "TWFu===="] {
+        let image = ImageContent::new(data.to_string(), "image/png".to_string(), None, None);
+        let error = image.decode_data().unwrap_err();
+        assert_eq!(error.code, RpcError::invalid_params().code);
+
+        let audio = AudioContent::new(data.to_string(), "audio/wav".to_string(), None, None);
+        assert!(audio.decode_data().is_err());
+    }
+}
+
+#[test]
+fn test_embedded_resource_validate() {
+    let text = TextResourceContents::new("fn main() {}", "file:///main.rs").with_mime_type("text/x-rust");
+    assert!(EmbeddedResource::new(text.into(), None, None).validate().is_ok());
+
+    let json = TextResourceContents::new("{}", "file:///config.json").with_mime_type("application/json; charset=utf-8");
+    assert!(EmbeddedResource::new(json.into(), None, None).validate().is_ok());
+
+    let blob = BlobResourceContents::new("AAEC", "file:///image.png").with_mime_type("image/png");
+    assert!(EmbeddedResource::new(blob.into(), None, None).validate().is_ok());
+
+    let untyped = TextResourceContents::new("hello", "file:///hello");
+    assert!(EmbeddedResource::new(untyped.into(), None, None).validate().is_ok());
+}
+
+#[test]
+fn test_embedded_resource_validate_inconsistent() {
+    let text = TextResourceContents::new("hello", "file:///hello.bin").with_mime_type("application/octet-stream");
+    let error = EmbeddedResource::new(text.into(), None, None).validate().unwrap_err();
+    assert_eq!(error.code, RpcError::invalid_params().code);
+    assert!(error.message.contains("application/octet-stream"));
+
+    let blob = BlobResourceContents::new("aGVsbG8=", "file:///hello.txt").with_mime_type("text/plain");
+    assert!(EmbeddedResource::new(blob.into(), None, None).validate().is_err());
+}
+
+#[test]
+fn test_tool_result_resource_link_content() {
+    let link = ResourceLink::new(
+        vec![],
+        "report".to_string(),
+        "file:///reports/q3.pdf".to_string(),
+        None,
+        None,
+        None,
+        Some("application/pdf".to_string()),
+        None,
+        None,
+    );
+    let result = CallToolResult::from_content(vec![ContentBlock::resource_link(link)]);
+
+    let value = serde_json::to_value(&result).unwrap();
+    assert_eq!(value["content"][0]["type"], "resource_link");
+
+    let result: CallToolResult = serde_json::from_value(value).unwrap();
+    let content = &result.content[0];
+    assert_eq!(content.content_type(), "resource_link");
+    let link = content.as_resource_link().unwrap();
+    assert_eq!(link.uri, "file:///reports/q3.pdf");
+    assert_eq!(link.mime_type.as_deref(), Some("application/pdf"));
+    assert!(content.as_text_content().is_err());
+}
+
+#[test]
+fn test_resource_contents_ext() {
+    use rust_mcp_schema::schema_utils::ResourceContentsExt;
+
+    let text = TextResourceContents::new("hello", "file:///notes.txt").with_mime_type("text/plain");
+    let blob = BlobResourceContents::new("aGVsbG8=", "file:///logo.png");
+
+    let contents: Vec<&dyn ResourceContentsExt> = vec![&text, &blob];
+    let uris: Vec<&str> = contents.iter().map(|contents| contents.uri()).collect();
+    assert_eq!(uris, vec!["file:///notes.txt", "file:///logo.png"]);
+    assert_eq!(text.mime_type(), Some("text/plain"));
+    assert_eq!(blob.mime_type(), None);
+
+    let resource = EmbeddedResourceResource::BlobResourceContents(blob);
+    assert_eq!(resource.uri(), "file:///logo.png");
+}
+
+#[test]
+fn test_read_resource_result_text_only() {
+    let text = TextResourceContents::new("hello", "file:///notes.txt");
+    let blob = BlobResourceContents::new("aGVsbG8=", "file:///logo.png");
+
+    let mixed = ReadResourceResult {
+        contents: vec![text.clone().into(), blob.into()],
+        meta: None,
+    };
+    assert!(!mixed.is_text_only());
+    let uris: Vec<&str> = mixed.text_contents().map(|text| text.uri.as_str()).collect();
+    assert_eq!(uris, vec!["file:///notes.txt"]);
+
+    let text_only = ReadResourceResult {
+        contents: vec![text.into()],
+        meta: None,
+    };
+    assert!(text_only.is_text_only());
+    assert_eq!(text_only.text_contents().count(), 1);
+}
+
+#[test]
+fn test_annotations_last_modified() {
+    let annotations = Annotations::default().with_last_modified("2025-01-12T17:00:58+02:00");
+    let value = serde_json::to_value(&annotations).unwrap();
+    assert_eq!(value, serde_json::json!({"lastModified": "2025-01-12T17:00:58+02:00"}));
+
+    let restored: Annotations = serde_json::from_value(value).unwrap();
+    assert_eq!(restored.last_modified.as_deref(), Some("2025-01-12T17:00:58+02:00"));
+
+    #[cfg(feature = "chrono")]
+    {
+        let datetime = restored.last_modified_datetime().unwrap();
+        assert_eq!(datetime.to_rfc3339(), "2025-01-12T15:00:58+00:00");
+        assert!(Annotations::default()
+            .with_last_modified("yesterday")
+            .last_modified_datetime()
+            .is_none());
+        assert!(Annotations::default().last_modified_datetime().is_none());
+    }
+}
+
+#[test]
+fn test_resource_contents_byte_len() {
+    let text = TextResourceContents::new("héllo", "file:///notes.txt");
+    assert_eq!(text.byte_len(), 6);
+
+    // "hello world" is 11 bytes
+    let blob = BlobResourceContents::new("aGVsbG8gd29ybGQ=", "file:///hello.bin");
+    assert_eq!(blob.byte_len().unwrap(), 11);
+    for (base64, len) in [("", 0), ("aGk=", 2), ("aGk", 2), ("aGVsbG8gd29ybGQ", 11), ("aGVsbG8=", 5)] {
+        assert_eq!(BlobResourceContents::new(base64, "file:///a.bin").byte_len().unwrap(), len);
+    }
+    // a single trailing character cannot encode a byte
+    assert!(BlobResourceContents::new("aGVsb", "file:///bad.bin").byte_len().is_err());
+    assert!(BlobResourceContents::new("not base64!", "file:///bad.bin")
+        .byte_len()
+        .is_err());
+
+    let result = ReadResourceResult {
+        contents: vec![text.into(), blob.into()],
+        meta: None,
+    };
+    assert_eq!(result.total_bytes().unwrap(), 17);
+
+    let invalid = ReadResourceResult {
+        contents: vec![BlobResourceContents::new("@@@", "file:///bad.bin").into()],
+        meta: None,
+    };
+    assert!(invalid.total_bytes().is_err());
+}
@@ -0,0 +1,193 @@
+use rust_mcp_schema::schema_utils::*;
+use rust_mcp_schema::*;
+
+#[test]
+fn test_rpc_error_code_kind() {
+    assert_eq!(RpcError::method_not_found().code, -32601);
+    assert_eq!(RpcError::method_not_found().code_kind(), Some(ErrorCodeKind::MethodNotFound));
+
+    let error: RpcError = SdkError::connection_closed().into();
+    assert_eq!(error.code, -32000);
+    assert_eq!(error.code_kind(), Some(ErrorCodeKind::ConnectionClosed));
+
+    let error = JsonrpcErrorResponse::create(
+        Some(RequestId::Integer(1)),
+        RpcErrorCodes::URL_ELICITATION_REQUIRED,
+        "elicitation required".to_string(),
+        None,
+    );
+    assert_eq!(error.error.code_kind(), Some(ErrorCodeKind::UrlElicitationRequired));
+
+    let mut error = RpcError::internal_error();
+    error.code = -31999;
+    assert_eq!(error.code_kind(), None);
+}
+
+#[test]
+fn test_rpc_error_is_retryable() {
+    let error: RpcError = SdkError::connection_closed().into();
+    assert!(error.is_retryable());
+    assert!(RpcError::internal_error().is_retryable());
+
+    let error = JsonrpcErrorResponse::new(SdkError::request_timeout(5000).into(), Some(RequestId::Integer(1)));
+    assert!(error.error().is_retryable());
+
+    assert!(!RpcError::invalid_params().is_retryable());
+    assert!(!RpcError::method_not_found().is_retryable());
+    assert!(!RpcError::parse_error().is_retryable());
+
+    let mut error = RpcError::internal_error();
+    error.code = -31999;
+    assert!(!error.is_retryable());
+}
+
+#[test]
+fn test_rpc_error_application_error() {
+    let error = RpcError::application_error(-31000, "Rate limited").unwrap();
+    assert_eq!(error.code, -31000);
+    assert_eq!(error.message, "Rate limited");
+    assert_eq!(error.code_kind(), None);
+
+    for code in [-32768, -32601, -32000] {
+        let error = RpcError::application_error(code, "Collides").unwrap_err();
+        assert_eq!(error.code, RpcError::invalid_params().code);
+    }
+}
+
+#[test]
+fn test_call_tool_error_keeps_source_chain() {
+    #[derive(Debug)]
+    struct ConnectionError;
+    impl std::fmt::Display for ConnectionError {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "connection refused")
+        }
+    }
+    impl std::error::Error for ConnectionError {}
+
+    #[derive(Debug)]
+    struct FetchError(ConnectionError);
+    impl std::fmt::Display for FetchError {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "failed to fetch weather data")
+        }
+    }
+    impl std::error::Error for FetchError {
+        fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+            Some(&self.0)
+        }
+    }
+
+    let error: RpcError = CallToolError::new(FetchError(ConnectionError)).into();
+    assert_eq!(error.message, "failed to fetch weather data");
+    assert_eq!(
+        error.data,
+        Some(serde_json::json!(["failed to fetch weather data", "connection refused"]))
+    );
+
+    let error: RpcError = CallToolError::from_message("boom").into();
+    assert_eq!(error.data, Some(serde_json::json!(["boom"])));
+}
+
+#[test]
+fn test_rpc_error_data_serde_roundtrip() {
+    // `None` data is omitted rather than serialized as null
+    let error = RpcError::internal_error().with_message("boom".to_string());
+    let value = serde_json::to_value(&error).unwrap();
+    assert!(value.as_object().unwrap().get("data").is_none());
+
+    // an explicit null is read back as `None`
+    let error: RpcError =
+        serde_json::from_value(serde_json::json!({"code": -32603, "message": "boom", "data": null})).unwrap();
+    assert!(error.data.is_none());
+    assert!(!serde_json::to_string(&error).unwrap().contains("data"));
+
+    // nested errors and an empty object survive a round trip unchanged
+    let nested = serde_json::json!({"errors": [{"code": -32602, "message": "bad", "data": {"field": "name"}}], "extra": {}});
+    let error = RpcError::invalid_params().with_data(Some(nested.clone()));
+    let restored: RpcError = serde_json::from_str(&serde_json::to_string(&error).unwrap()).unwrap();
+    assert_eq!(restored.data, Some(nested));
+
+    let error = RpcError::invalid_params().with_data(Some(serde_json::json!({})));
+    let restored: RpcError = serde_json::from_str(&serde_json::to_string(&error).unwrap()).unwrap();
+    assert_eq!(restored.data, Some(serde_json::json!({})));
+}
+
+#[test]
+fn test_rpc_error_localization_key() {
+    let error = RpcError::invalid_params()
+        .with_message("Unknown city".to_string())
+        .with_localization_key("weather.unknown_city");
+    assert_eq!(error.message, "Unknown city");
+    assert_eq!(error.data, Some(serde_json::json!({"_i18n": "weather.unknown_city"})));
+    assert_eq!(error.localization_key(), Some("weather.unknown_city"));
+
+    // existing data is preserved
+    let error = RpcError::internal_error()
+        .with_data(Some(serde_json::json!({"city": "Atlantis"})))
+        .with_localization_key("weather.unavailable");
+    assert_eq!(
+        error.data,
+        Some(serde_json::json!({"city": "Atlantis", "_i18n": "weather.unavailable"}))
+    );
+
+    let error = RpcError::internal_error()
+        .with_data(Some(serde_json::json!("timeout")))
+        .with_localization_key("net.timeout");
+    assert_eq!(
+        error.data,
+        Some(serde_json::json!({"details": "timeout", "_i18n": "net.timeout"}))
+    );
+    assert_eq!(RpcError::internal_error().localization_key(), None);
+}
+
+#[test]
+fn test_rpc_error_from_serde_json_error() {
+    fn parse_params(raw: &str) -> std::result::Result<CallToolRequestParams, RpcError> {
+        Ok(serde_json::from_str(raw)?)
+    }
+
+    let error = parse_params(r#"{"name": 42}"#).unwrap_err();
+    assert_eq!(error.code, RpcError::parse_error().code);
+    assert_eq!(error.message, RpcError::parse_error().message);
+    let details = error.data.unwrap()["details"].as_str().unwrap().to_string();
+    assert!(details.contains("invalid type"), "{details}");
+
+    assert!(parse_params(r#"{"name": "add"}"#).is_ok());
+}
+
+#[test]
+fn test_rpc_error_equality_and_hash() {
+    assert_eq!(RpcError::invalid_params(), RpcError::invalid_params());
+
+    let with_data = RpcError::invalid_params().with_data(Some(serde_json::json!({ "details": "missing 'a'" })));
+    assert_ne!(RpcError::invalid_params(), with_data);
+    assert_ne!(RpcError::invalid_params(), RpcError::invalid_request());
+
+    let errors: std::collections::HashSet<RpcError> = [
+        RpcError::invalid_params(),
+        RpcError::invalid_params(),
+        with_data.clone(),
+        with_data,
+    ]
+    .into_iter()
+    .collect();
+    assert_eq!(errors.len(), 2);
+}
+
+#[cfg(feature = "anyhow")]
+#[test]
+fn test_call_tool_error_from_anyhow() {
+    use anyhow::Context;
+
+    fn run_tool() -> std::result::Result<(), CallToolError> {
+        let result: std::result::Result<(), std::io::Error> = Err(std::io::Error::other("disk full"));
+        result.context("failed to write report")?;
+        Ok(())
+    }
+
+    let error = run_tool().unwrap_err();
+    assert_eq!(error.to_string(), "failed to write report");
+    let source = std::error::Error::source(&error).unwrap();
+    assert_eq!(source.to_string(), "disk full");
+}
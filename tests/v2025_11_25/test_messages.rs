@@ -0,0 +1,756 @@
+use rust_mcp_schema::schema_utils::*;
+use rust_mcp_schema::*;
+
+#[test]
+fn test_server_message_priority_ordering() {
+    let mut messages: Vec<ServerMessage> = vec![
+        notify_tools_changed(),
+        ServerJsonrpcRequest::new(RequestId::Integer(1), RequestFromServer::ListRootsRequest(None)).into(),
+        ServerJsonrpcResponse::new(RequestId::Integer(2), Result::default().into()).into(),
+        ServerMessage::Error(JsonrpcErrorResponse::new(
+            RpcError::internal_error(),
+            Some(RequestId::Integer(3)),
+        )),
+        notify_prompts_changed(),
+    ];
+
+    messages.sort_by_key(|message| message.priority());
+
+    let message_types: Vec<MessageTypes> = messages.iter().map(|message| message.message_type()).collect();
+    assert_eq!(
+        message_types,
+        vec![
+            MessageTypes::Error,
+            MessageTypes::Response,
+            MessageTypes::Request,
+            MessageTypes::Notification,
+            MessageTypes::Notification
+        ]
+    );
+}
+
+#[test]
+fn test_message_to_value_round_trip() {
+    let message: ClientMessage = ClientJsonrpcRequest::new(
+        RequestId::Integer(1),
+        RequestFromClient::CallToolRequest(CallToolRequestParams::new("add_numbers")),
+    )
+    .into();
+    let value = message.to_value().unwrap();
+    assert_eq!(value["method"], "tools/call");
+    let message = ClientMessage::try_from(value).unwrap();
+    assert!(matches!(
+        message,
+        ClientMessage::Request(ClientJsonrpcRequest::CallToolRequest(request)) if request.tool_name() == "add_numbers"
+    ));
+
+    let message = notify_tools_changed();
+    let value = message.to_value().unwrap();
+    let message = ServerMessage::try_from(value).unwrap();
+    assert!(matches!(
+        message,
+        ServerMessage::Notification(ServerJsonrpcNotification::ToolListChangedNotification(_))
+    ));
+
+    let error = ServerMessage::try_from(serde_json::json!({ "foo": "bar" })).unwrap_err();
+    assert_eq!(error.code, RpcError::parse_error().code);
+}
+
+#[test]
+fn test_message_to_pretty_string() {
+    let message: ServerMessage = notify_tools_changed();
+    let pretty = message.to_pretty_string();
+    assert!(pretty.contains('\n'));
+    assert!(!message.to_string().contains('\n'));
+    assert_eq!(
+        serde_json::from_str::<serde_json::Value>(&pretty).unwrap(),
+        serde_json::from_str::<serde_json::Value>(&message.to_string()).unwrap()
+    );
+
+    let message: ClientMessage =
+        ClientJsonrpcRequest::new(RequestId::Integer(1), RequestFromClient::PingRequest(None)).into();
+    assert!(message.to_pretty_string().contains('\n'));
+    assert!(!message.to_string().contains('\n'));
+
+    let messages = ClientMessages::Batch(vec![message]);
+    assert!(messages.to_pretty_string().contains('\n'));
+    assert!(!messages.to_string().contains('\n'));
+
+    let messages = ServerMessages::Single(notify_prompts_changed());
+    assert!(messages.to_pretty_string().contains('\n'));
+}
+
+#[test]
+fn test_server_message_is_response_to() {
+    let request = ClientJsonrpcRequest::new(RequestId::Integer(7), RequestFromClient::ListToolsRequest(None));
+    let list_tools_result = ListToolsResult {
+        meta: None,
+        next_cursor: None,
+        tools: vec![],
+    };
+
+    let response: ServerMessage = ServerJsonrpcResponse::new(RequestId::Integer(7), list_tools_result.clone().into()).into();
+    assert!(response.is_response_to(&request));
+
+    let error = ServerMessage::Error(JsonrpcErrorResponse::new(
+        RpcError::internal_error(),
+        Some(RequestId::Integer(7)),
+    ));
+    assert!(error.is_response_to(&request));
+
+    // id mismatch
+    let response: ServerMessage = ServerJsonrpcResponse::new(RequestId::Integer(8), list_tools_result.into()).into();
+    assert!(!response.is_response_to(&request));
+
+    // result type does not fit the method
+    let response: ServerMessage = ServerJsonrpcResponse::new(RequestId::Integer(7), Result::default().into()).into();
+    assert!(!response.is_response_to(&request));
+
+    assert!(!notify_tools_changed().is_response_to(&request));
+}
+
+#[test]
+fn test_client_messages_partition() {
+    let messages = ClientMessages::Batch(vec![
+        ClientJsonrpcRequest::new(RequestId::Integer(1), RequestFromClient::PingRequest(None)).into(),
+        ClientJsonrpcNotification::new(NotificationFromClient::RootsListChangedNotification(None)).into(),
+        ClientJsonrpcRequest::new(RequestId::Integer(2), RequestFromClient::ListToolsRequest(None)).into(),
+    ]);
+
+    let (requests, notifications, responses, errors) = messages.partition();
+    assert_eq!(requests.len(), 2);
+    assert_eq!(notifications.len(), 1);
+    assert!(responses.is_empty());
+    assert!(errors.is_empty());
+    assert_eq!(requests[1].request_id(), &RequestId::Integer(2));
+
+    let (requests, notifications, _, _) = ServerMessages::Single(notify_tools_changed()).partition();
+    assert!(requests.is_empty());
+    assert_eq!(notifications.len(), 1);
+}
+
+#[test]
+fn test_message_into_parts() {
+    let message: ServerMessage =
+        ServerJsonrpcRequest::new(RequestId::Integer(1), RequestFromServer::ListRootsRequest(None)).into();
+    let (id, payload) = message.into_parts();
+    assert_eq!(id, Some(RequestId::Integer(1)));
+    assert!(matches!(
+        payload,
+        MessageFromServer::RequestFromServer(RequestFromServer::ListRootsRequest(None))
+    ));
+    let message = ServerMessage::from_message(payload, id).unwrap();
+    assert!(matches!(
+        message,
+        ServerMessage::Request(ServerJsonrpcRequest::ListRootsRequest(_))
+    ));
+
+    let message: ServerMessage = ServerJsonrpcResponse::new(RequestId::Integer(2), Result::default().into()).into();
+    let (id, payload) = message.into_parts();
+    assert_eq!(id, Some(RequestId::Integer(2)));
+    assert!(matches!(
+        payload,
+        MessageFromServer::ResultFromServer(ResultFromServer::Result(_))
+    ));
+
+    let (id, payload) = notify_tools_changed().into_parts();
+    assert_eq!(id, None);
+    assert!(matches!(
+        payload,
+        MessageFromServer::NotificationFromServer(NotificationFromServer::ToolListChangedNotification(_))
+    ));
+
+    let message = ServerMessage::Error(JsonrpcErrorResponse::new(
+        RpcError::method_not_found(),
+        Some(RequestId::Integer(3)),
+    ));
+    let (id, payload) = message.into_parts();
+    assert_eq!(id, Some(RequestId::Integer(3)));
+    assert!(matches!(payload, MessageFromServer::Error(error) if error.code == RpcError::method_not_found().code));
+
+    let message: ClientMessage =
+        ClientJsonrpcRequest::new(RequestId::Integer(4), RequestFromClient::PingRequest(None)).into();
+    let (id, payload) = message.into_parts();
+    assert_eq!(id, Some(RequestId::Integer(4)));
+    assert!(matches!(
+        payload,
+        MessageFromClient::RequestFromClient(RequestFromClient::PingRequest(None))
+    ));
+}
+
+#[test]
+fn test_parse_client_message_with_limits() {
+    let message = r#"{"jsonrpc":"2.0","id":1,"method":"tools/call","params":{"name":"echo","arguments":{"a":[1,[2]]}}}"#;
+    assert!(parse_client_message_with_limits(message, 8).is_ok());
+
+    let nested = format!(
+        r#"{{"jsonrpc":"2.0","id":1,"method":"tools/call","params":{{"name":"echo","arguments":{{"a":{}1{}}}}}}}"#,
+        "[".repeat(100),
+        "]".repeat(100)
+    );
+    let error = parse_client_message_with_limits(&nested, 32).unwrap_err();
+    assert_eq!(error.code, RpcError::invalid_request().code);
+
+    // brackets inside strings do not count towards the depth
+    let message = r#"{"jsonrpc":"2.0","id":1,"method":"tools/call","params":{"name":"[[[[[[[[\"{{{{"}}"#;
+    assert!(parse_client_message_with_limits(message, 2).is_ok());
+}
+
+#[test]
+fn test_framed_client_message_round_trip() {
+    let message: ClientMessage =
+        ClientJsonrpcRequest::new(RequestId::Integer(1), RequestFromClient::PingRequest(None)).into();
+    let framed = FramedClientMessage::new("session-1", 42, message);
+
+    let value = serde_json::to_value(&framed).unwrap();
+    assert_eq!(value["sessionId"], "session-1");
+    assert_eq!(value["seq"], 42);
+    assert_eq!(value["message"]["method"], "ping");
+
+    let framed: FramedClientMessage = serde_json::from_value(value).unwrap();
+    assert_eq!(framed.session_id, "session-1");
+    assert_eq!(framed.seq, 42);
+    assert!(matches!(
+        framed.message,
+        ClientMessage::Request(ClientJsonrpcRequest::PingRequest(request)) if request.id == RequestId::Integer(1)
+    ));
+}
+
+#[test]
+fn test_response_and_notification_builders() {
+    let message = ResponseBuilder::new(RequestId::Integer(5)).result(Result::default());
+    assert!(matches!(&message, ServerMessage::Response(response) if response.id == RequestId::Integer(5)));
+
+    let message = ResponseBuilder::new(RequestId::String("abc".to_string())).error(RpcError::method_not_found());
+    assert!(matches!(
+        &message,
+        ServerMessage::Error(error) if error.id == Some(RequestId::String("abc".to_string()))
+    ));
+
+    // notifications have no id to set; see the compile_fail example on `NotificationBuilder`
+    let message = NotificationBuilder::new().notification(NotificationFromServer::ToolListChangedNotification(None));
+    assert!(matches!(
+        &message,
+        ServerMessage::Notification(ServerJsonrpcNotification::ToolListChangedNotification(_))
+    ));
+    assert!(message.request_id().is_none());
+}
+
+#[test]
+fn test_borrowed_messages_iteration() {
+    let messages = ClientMessages::Batch(vec![
+        ClientJsonrpcRequest::new(RequestId::Integer(1), RequestFromClient::PingRequest(None)).into(),
+        ClientJsonrpcNotification::new(NotificationFromClient::RootsListChangedNotification(None)).into(),
+    ]);
+
+    let requests = (&messages).into_iter().filter(|message| message.is_request()).count();
+    assert_eq!(requests, 1);
+    let mut count = 0;
+    for message in &messages {
+        assert!(message.is_request() || message.is_notification());
+        count += 1;
+    }
+    assert_eq!(count, 2);
+    assert!(messages.is_batch());
+
+    let messages = ServerMessages::Single(notify_tools_changed());
+    assert_eq!((&messages).into_iter().count(), 1);
+    assert_eq!((&messages).into_iter().count(), 1);
+}
+
+#[test]
+fn test_message_with_request_id() {
+    let message: ClientMessage = ClientJsonrpcRequest::new(
+        RequestId::Integer(1),
+        RequestFromClient::CallToolRequest(CallToolRequestParams::new("add")),
+    )
+    .into();
+    let message = message.with_request_id(RequestId::String("upstream-a:1".to_string()));
+    assert_eq!(message.request_id(), Some(&RequestId::String("upstream-a:1".to_string())));
+    assert!(matches!(
+        message,
+        ClientMessage::Request(ClientJsonrpcRequest::CallToolRequest(request)) if request.tool_name() == "add"
+    ));
+
+    let message = ServerMessage::Error(JsonrpcErrorResponse::new(RpcError::internal_error(), None));
+    let message = message.with_request_id(RequestId::Integer(9));
+    assert_eq!(message.request_id(), Some(&RequestId::Integer(9)));
+
+    let message = ResponseBuilder::new(RequestId::Integer(2)).result(Result::default());
+    assert_eq!(
+        message.with_request_id(RequestId::Integer(3)).request_id(),
+        Some(&RequestId::Integer(3))
+    );
+
+    let notification = notify_tools_changed();
+    let expected = notification.to_value().unwrap();
+    let notification = notification.with_request_id(RequestId::Integer(4));
+    assert!(notification.request_id().is_none());
+    assert_eq!(notification.to_value().unwrap(), expected);
+}
+
+#[test]
+fn test_message_constructors() {
+    let message = ClientMessage::new_request(RequestId::Integer(1), RequestFromClient::PingRequest(None));
+    assert!(matches!(
+        &message,
+        ClientMessage::Request(ClientJsonrpcRequest::PingRequest(_))
+    ));
+    assert_eq!(message.request_id(), Some(&RequestId::Integer(1)));
+
+    let message = ClientMessage::new_notification(NotificationFromClient::RootsListChangedNotification(None));
+    assert!(matches!(
+        message,
+        ClientMessage::Notification(ClientJsonrpcNotification::RootsListChangedNotification(_))
+    ));
+
+    let message = ClientMessage::new_response(
+        RequestId::Integer(2),
+        ListRootsResult {
+            meta: None,
+            roots: vec![],
+        },
+    );
+    assert!(matches!(&message, ClientMessage::Response(response) if response.id == RequestId::Integer(2)));
+
+    let message = ServerMessage::new_request(RequestId::Integer(3), RequestFromServer::ListRootsRequest(None));
+    assert!(matches!(
+        message,
+        ServerMessage::Request(ServerJsonrpcRequest::ListRootsRequest(_))
+    ));
+
+    let message = ServerMessage::new_notification(NotificationFromServer::ToolListChangedNotification(None));
+    assert!(message.is_notification());
+
+    let message = ServerMessage::new_response(RequestId::Integer(4), Result::default());
+    assert!(matches!(&message, ServerMessage::Response(response) if response.id == RequestId::Integer(4)));
+}
+
+#[test]
+fn test_content_length_framing() {
+    let first = notify_tools_changed();
+    let second = ServerMessage::new_response(RequestId::Integer(1), Result::default());
+    let stream = format!("{}{}", encode_framed(&first), encode_framed(&second));
+    assert!(stream.starts_with(&format!("Content-Length: {}\r\n\r\n", first.to_string().len())));
+
+    let mut reader = std::io::Cursor::new(stream.into_bytes());
+    let decoded = decode_framed(&mut reader).unwrap().unwrap();
+    assert!(matches!(
+        decoded,
+        ServerMessage::Notification(ServerJsonrpcNotification::ToolListChangedNotification(_))
+    ));
+    let decoded = decode_framed(&mut reader).unwrap().unwrap();
+    assert_eq!(decoded.request_id(), Some(&RequestId::Integer(1)));
+    assert!(decode_framed(&mut reader).unwrap().is_none());
+
+    let framed = encode_framed(&first);
+    let mut reader = std::io::Cursor::new(framed.as_bytes()[..framed.len() - 5].to_vec());
+    let error = decode_framed(&mut reader).unwrap_err();
+    assert_eq!(error.code, RpcError::parse_error().code);
+    assert!(error.message.contains("Truncated"));
+
+    let mut reader = std::io::Cursor::new(b"Content-Type: application/json\r\n\r\n{}".to_vec());
+    let error = decode_framed(&mut reader).unwrap_err();
+    assert!(error.message.contains("Missing Content-Length"));
+}
+
+#[test]
+fn test_decode_framed_rejects_oversized_frame() {
+    let mut reader = std::io::Cursor::new(b"Content-Length: 18446744073709551615\r\n\r\n{}".to_vec());
+    let error = decode_framed(&mut reader).unwrap_err();
+    assert_eq!(error.code, RpcError::parse_error().code);
+    assert!(error.message.contains("maximum frame size"));
+
+    let header = format!("Content-Length: {}\r\n\r\n", MAX_FRAME_SIZE + 1);
+    let mut reader = std::io::Cursor::new(header.into_bytes());
+    let error = decode_framed(&mut reader).unwrap_err();
+    assert_eq!(error.code, RpcError::parse_error().code);
+}
+
+#[test]
+fn test_decode_framed_rejects_oversized_header_line() {
+    // a header line without a newline is not buffered past the cap
+    let mut reader = std::io::Cursor::new(vec![b'x'; MAX_HEADER_LINE * 4]);
+    let error = decode_framed(&mut reader).unwrap_err();
+    assert_eq!(error.code, RpcError::parse_error().code);
+    assert!(error.message.contains("Malformed frame header"));
+    assert_eq!(reader.position(), MAX_HEADER_LINE as u64);
+
+    // a long header line within the cap is still accepted
+    let body = r#"{"jsonrpc":"2.0","id":1,"result":{}}"#;
+    let padding = "x".repeat(MAX_HEADER_LINE - "X-Padding: \r\n".len());
+    let frame = format!("X-Padding: {padding}\r\nContent-Length: {}\r\n\r\n{body}", body.len());
+    let mut reader = std::io::Cursor::new(frame.into_bytes());
+    let decoded = decode_framed(&mut reader).unwrap().unwrap();
+    assert_eq!(decoded.request_id(), Some(&RequestId::Integer(1)));
+}
+
+#[test]
+fn test_parse_server_message_with_limits() {
+    let small = r#"{"jsonrpc":"2.0","id":1,"result":{"tools":[]}}"#;
+    let message = parse_server_message_with_limits(small, 64).unwrap();
+    assert_eq!(message.request_id(), Some(&RequestId::Integer(1)));
+
+    let large = format!(
+        r#"{{"jsonrpc":"2.0","result":{{"content":[{{"type":"text","text":"{}"}}]}},"id":2}}"#,
+        "x".repeat(1024)
+    );
+    let error = parse_server_message_with_limits(&large, 512).unwrap_err();
+    assert_eq!(error.code, RpcError::invalid_request().code);
+    assert!(parse_server_message_with_limits(&large, 4096).is_ok());
+
+    // a large message without a result (e.g. a notification) is not subject to the result limit
+    let notification = format!(
+        r#"{{"jsonrpc":"2.0","method":"notifications/message","params":{{"level":"info","data":"{}"}}}}"#,
+        "y".repeat(1024)
+    );
+    assert!(parse_server_message_with_limits(&notification, 512).is_ok());
+}
+
+#[test]
+fn test_message_span_name() {
+    let request = ClientMessage::new_request(
+        RequestId::Integer(1),
+        RequestFromClient::CallToolRequest(CallToolRequestParams::new("add")),
+    );
+    assert_eq!(request.span_name(), "request:tools/call");
+
+    let notification =
+        ServerMessage::new_notification(NotificationFromServer::ProgressNotification(ProgressNotificationParams {
+            message: None,
+            meta: None,
+            progress: 0.5,
+            progress_token: ProgressToken::Integer(1),
+            total: None,
+        }));
+    assert_eq!(notification.span_name(), "notification:notifications/progress");
+
+    let response = ServerMessage::new_response(RequestId::Integer(1), Result::default());
+    assert_eq!(response.span_name(), "response");
+
+    let error = ClientMessage::Error(JsonrpcErrorResponse::new(
+        RpcError::internal_error(),
+        Some(RequestId::Integer(1)),
+    ));
+    assert_eq!(error.span_name(), "error");
+}
+
+#[test]
+fn test_server_messages_broadcast() {
+    let messages = ServerMessages::broadcast(NotificationFromServer::ToolListChangedNotification(None), 3);
+    assert!(messages.is_batch());
+    let (requests, notifications, responses, errors) = messages.partition();
+    assert_eq!(notifications.len(), 3);
+    assert!(requests.is_empty() && responses.is_empty() && errors.is_empty());
+    assert!(notifications
+        .iter()
+        .all(|n| matches!(n, ServerJsonrpcNotification::ToolListChangedNotification(_))));
+
+    assert_eq!(
+        ServerMessages::broadcast(NotificationFromServer::ToolListChangedNotification(None), 0).to_string(),
+        "[]"
+    );
+}
+
+#[test]
+fn test_extend_messages_promotes_single_to_batch() {
+    let ping = |id| ClientMessage::new_request(RequestId::Integer(id), RequestFromClient::PingRequest(None));
+
+    let mut messages = ClientMessages::Single(ping(1));
+    messages.extend(std::iter::empty());
+    assert!(!messages.is_batch());
+
+    messages.extend(vec![ping(2), ping(3)]);
+    assert!(messages.is_batch());
+    let ids: Vec<_> = messages
+        .into_iter()
+        .filter_map(|message| message.request_id().cloned())
+        .collect();
+    assert_eq!(ids, vec![RequestId::Integer(1), RequestId::Integer(2), RequestId::Integer(3)]);
+
+    let mut messages = ServerMessages::Batch(vec![]);
+    messages.extend(vec![ServerMessage::new_response(RequestId::Integer(1), Result::default())]);
+    assert!(matches!(messages, ServerMessages::Batch(ref batch) if batch.len() == 1));
+
+    let mut messages = MessagesFromServer::Single(MessageFromServer::from(Result::default()));
+    messages.extend([MessageFromServer::from(Result::default())]);
+    assert!(matches!(messages, MessagesFromServer::Batch(ref batch) if batch.len() == 2));
+}
+
+#[test]
+fn test_client_message_parse_with_diagnostics() {
+    // carries both `method` and `result`, so it is not clearly a request or a response
+    let ambiguous = r#"{"jsonrpc":"2.0","id":1,"method":"ping","result":{}}"#;
+    let (result, diagnostics) = ClientMessage::parse_with_diagnostics(ambiguous);
+    assert!(matches!(result, Ok(ClientMessage::Request(_))));
+    assert_eq!(diagnostics.len(), 4);
+    for (line, variant) in diagnostics.iter().zip(["Request", "Notification", "Response", "Error"]) {
+        assert!(line.starts_with(&format!("{variant}: ")), "{line}");
+    }
+    assert_eq!(diagnostics[0], "Request: matched (selected)");
+    assert_eq!(diagnostics[1], "Notification: notifications must not carry an id");
+    assert!(diagnostics[2].contains("unknown field `method`"));
+    assert!(diagnostics[3].contains("missing field `error`"));
+
+    let (result, diagnostics) = ClientMessage::parse_with_diagnostics(r#"{"jsonrpc":"2.0","id":1,"method":"ping"}"#);
+    assert!(result.is_ok());
+    assert_eq!(diagnostics[0], "Request: matched (selected)");
+
+    let (result, diagnostics) = ClientMessage::parse_with_diagnostics("{not json");
+    assert!(result.is_err());
+    assert!(diagnostics[0].starts_with("json: "));
+}
+
+#[test]
+fn test_message_anonymize() {
+    let request = r#"{"jsonrpc":"2.0","id":"req-1","method":"tools/call",
+            "params":{"name":"send_email","arguments":{"to":"jane@example.com","retries":2,"urgent":true}}}"#
+        .parse::<ClientMessage>()
+        .unwrap();
+    let value = serde_json::to_value(request.anonymize().unwrap()).unwrap();
+    assert_eq!(value["method"], "tools/call");
+    assert_eq!(value["id"], "req-1");
+    assert_eq!(value["params"]["name"], "send_email");
+    assert_eq!(value["params"]["arguments"]["to"], "xxxxxxxxxxxxxxxx");
+    assert_eq!(value["params"]["arguments"]["retries"], 2);
+    assert_eq!(value["params"]["arguments"]["urgent"], true);
+
+    let response = r#"{"jsonrpc":"2.0","id":7,"result":{"content":[{"type":"text","text":"Jane's balance is 42€"}]}}"#
+        .parse::<ServerMessage>()
+        .unwrap();
+    let anonymized = response.anonymize().unwrap();
+    assert!(matches!(anonymized, ServerMessage::Response(_)));
+    let value = serde_json::to_value(&anonymized).unwrap();
+    assert_eq!(value["id"], 7);
+    assert_eq!(value["result"]["content"][0]["type"], "text");
+    assert_eq!(
+        value["result"]["content"][0]["text"],
+        "x".repeat("Jane's balance is 42€".chars().count())
+    );
+
+    let notification =
+        r#"{"jsonrpc":"2.0","method":"notifications/message","params":{"level":"info","data":"user jane logged in"}}"#
+            .parse::<ServerMessage>()
+            .unwrap();
+    let value = serde_json::to_value(notification.anonymize().unwrap()).unwrap();
+    assert_eq!(value["method"], "notifications/message");
+    assert_eq!(value["params"]["level"], "info");
+    assert_eq!(value["params"]["data"], "xxxxxxxxxxxxxxxxxxx");
+
+    let error =
+        r#"{"jsonrpc":"2.0","id":8,"error":{"code":-32602,"message":"no user jane","data":{"email":"jane@example.com"}}}"#
+            .parse::<ServerMessage>()
+            .unwrap();
+    let anonymized = error.anonymize().unwrap();
+    assert!(anonymized.is_error());
+    let value = serde_json::to_value(&anonymized).unwrap();
+    assert_eq!(value["id"], 8);
+    assert_eq!(value["error"]["code"], -32602);
+    assert_eq!(value["error"]["message"], "xxxxxxxxxxxx");
+    assert_eq!(value["error"]["data"]["email"], "xxxxxxxxxxxxxxxx");
+}
+
+#[test]
+fn test_message_semantically_eq_ignores_jsonrpc() {
+    let received = r#"{"jsonrpc":"1.0","id":1,"result":{}}"#.parse::<ClientMessage>().unwrap();
+    let expected = ClientMessage::new_response(RequestId::Integer(1), Result::default());
+    assert!(received.semantically_eq(&expected));
+    assert_ne!(received.to_string(), expected.to_string());
+
+    let other_id = ClientMessage::new_response(RequestId::Integer(2), Result::default());
+    assert!(!received.semantically_eq(&other_id));
+
+    let first = r#"{"jsonrpc":"2.0","method":"notifications/message","params":{"level":"info","data":"a"}}"#
+        .parse::<ServerMessage>()
+        .unwrap();
+    let reordered = r#"{"params":{"data":"a","level":"info"},"method":"notifications/message","jsonrpc":"2.0"}"#
+        .parse::<ServerMessage>()
+        .unwrap();
+    let different = r#"{"jsonrpc":"2.0","method":"notifications/message","params":{"level":"info","data":"b"}}"#
+        .parse::<ServerMessage>()
+        .unwrap();
+    assert!(first.semantically_eq(&reordered));
+    assert!(!first.semantically_eq(&different));
+}
+
+#[test]
+fn test_try_detect_message_type() {
+    let request = serde_json::json!({"jsonrpc": "2.0", "id": 1, "method": "ping"});
+    let notification = serde_json::json!({"jsonrpc": "2.0", "method": "notifications/initialized"});
+    let response = serde_json::json!({"jsonrpc": "2.0", "id": 1, "result": {}});
+    let error = serde_json::json!({"jsonrpc": "2.0", "id": 1, "error": {"code": -32600, "message": "Invalid"}});
+
+    assert_eq!(try_detect_message_type(&request).unwrap(), MessageTypes::Request);
+    assert_eq!(try_detect_message_type(&notification).unwrap(), MessageTypes::Notification);
+    assert_eq!(try_detect_message_type(&response).unwrap(), MessageTypes::Response);
+    assert_eq!(try_detect_message_type(&error).unwrap(), MessageTypes::Error);
+
+    // error responses may omit the id when it could not be determined
+    let error = serde_json::json!({"jsonrpc": "2.0", "error": {"code": -32700, "message": "Parse error"}});
+    assert_eq!(try_detect_message_type(&error).unwrap(), MessageTypes::Error);
+
+    let err = try_detect_message_type(&serde_json::json!({})).unwrap_err();
+    assert_eq!(err.code, RpcError::invalid_request().code);
+    assert!(try_detect_message_type(&serde_json::json!({"id": 1})).is_err());
+}
+
+#[test]
+fn test_server_message_as_outcome() {
+    let success: ServerMessage = ServerJsonrpcResponse::new(RequestId::Integer(7), Result::default().into()).into();
+    let (id, outcome) = success.as_outcome().unwrap();
+    assert_eq!(id, RequestId::Integer(7));
+    assert!(matches!(outcome, ResponseOutcome::Ok(_)));
+
+    let failure = ServerMessage::Error(JsonrpcErrorResponse::new(
+        RpcError::method_not_found(),
+        Some(RequestId::String("abc".to_string())),
+    ));
+    let (id, outcome) = failure.as_outcome().unwrap();
+    assert_eq!(id, RequestId::String("abc".to_string()));
+    match outcome {
+        ResponseOutcome::Err(error) => assert_eq!(error.code, RpcError::method_not_found().code),
+        ResponseOutcome::Ok(_) => panic!("expected an error outcome"),
+    }
+
+    assert!(notify_tools_changed().as_outcome().is_none());
+}
+
+#[test]
+fn test_message_write_to() {
+    let message: ClientMessage =
+        ClientJsonrpcRequest::new(RequestId::Integer(1), RequestFromClient::PingRequest(None)).into();
+    let mut buffer: Vec<u8> = Vec::new();
+    message.write_to(&mut buffer).unwrap();
+    assert_eq!(String::from_utf8(buffer).unwrap(), message.to_string());
+
+    let message = notify_tools_changed();
+    let mut buffer: Vec<u8> = Vec::new();
+    message.write_framed_to(&mut buffer).unwrap();
+    assert_eq!(String::from_utf8(buffer).unwrap(), format!("{message}\n"));
+}
+
+#[test]
+fn test_peek_method() {
+    let request = r#"{"jsonrpc":"2.0","id":1,"method":"tools/call","params":{"name":"add","arguments":{"a":1}}}"#;
+    assert_eq!(peek_method(request).as_deref(), Some("tools/call"));
+
+    let notification = r#"{"params":{},"method":"notifications/initialized","jsonrpc":"2.0"}"#;
+    assert_eq!(peek_method(notification).as_deref(), Some("notifications/initialized"));
+
+    let response = r#"{"jsonrpc":"2.0","id":1,"result":{}}"#;
+    assert_eq!(peek_method(response), None);
+    assert_eq!(peek_method("not json"), None);
+}
+
+#[test]
+fn test_client_message_notification_constructors() {
+    let initialized = ClientMessage::initialized();
+    assert!(initialized.is_notification());
+    assert_eq!(initialized.method(), Some("notifications/initialized"));
+
+    let roots_list_changed = ClientMessage::roots_list_changed();
+    assert!(roots_list_changed.is_notification());
+    assert_eq!(roots_list_changed.method(), Some("notifications/roots/list_changed"));
+}
+
+#[test]
+fn test_response_with_null_error_parses_as_response() {
+    let payload = r#"{"jsonrpc":"2.0","id":1,"result":{"tools":[]},"error":null}"#;
+
+    let message = payload.parse::<ServerMessage>().unwrap();
+    assert_eq!(message.message_type(), MessageTypes::Response);
+    assert_eq!(message.request_id(), Some(&RequestId::Integer(1)));
+
+    let message: ServerMessage = serde_json::from_str(payload).unwrap();
+    assert!(message.is_response());
+
+    let message = r#"{"jsonrpc":"2.0","id":1,"result":{},"error":null}"#.parse::<ClientMessage>().unwrap();
+    assert!(message.is_response());
+}
+
+#[test]
+fn test_shared_message_serialization() {
+    let message = notify_tools_changed();
+    let shared: SharedServerMessage = message.clone().into();
+    let subscriber = shared.clone();
+    assert_eq!(
+        serde_json::to_string(&subscriber).unwrap(),
+        serde_json::to_string(&message).unwrap()
+    );
+
+    let message = ClientMessage::initialized();
+    let shared = SharedClientMessage::from(message.clone());
+    assert_eq!(
+        serde_json::to_value(&shared).unwrap(),
+        serde_json::to_value(&message).unwrap()
+    );
+}
+
+#[test]
+fn test_parse_client_message_strict_version() {
+    let message = r#"{"jsonrpc":"2.0","id":1,"method":"initialize","params":{"protocolVersion":"2025-11-25","capabilities":{"elicitation":{}},"clientInfo":{"name":"client","version":"1.0.0"}}}"#;
+    assert!(parse_client_message_strict_version(message).unwrap().is_request());
+
+    // `extensions` is only defined by the draft schema
+    let message = r#"{"jsonrpc":"2.0","id":1,"method":"initialize","params":{"protocolVersion":"2025-11-25","capabilities":{"extensions":{}},"clientInfo":{"name":"client","version":"1.0.0"}}}"#;
+    let error = parse_client_message_strict_version(message).unwrap_err();
+    assert_eq!(error.code, RpcError::invalid_request().code);
+    assert!(error.message.contains("params.capabilities.extensions"));
+
+    // custom requests and free-form members are not checked
+    let message = r#"{"jsonrpc":"2.0","id":2,"method":"acme/install","params":{"extensions":["a"],"mode":"fast"}}"#;
+    assert!(parse_client_message_strict_version(message).is_ok());
+    let message =
+        r#"{"jsonrpc":"2.0","id":3,"method":"tools/call","params":{"name":"render","arguments":{"extensions":true}}}"#;
+    assert!(parse_client_message_strict_version(message).is_ok());
+    let message = r#"{"jsonrpc":"2.0","id":4,"method":"ping","params":null}"#;
+    assert!(parse_client_message_strict_version(message).is_ok());
+}
+
+#[test]
+fn test_notification_matches_method() {
+    let ServerMessage::Notification(notification) = notify_resource_updated("file:///project/README.md") else {
+        panic!("expected a notification");
+    };
+    let notification: NotificationFromServer = notification.into();
+    assert!(notification.matches_method("notifications/resources/updated"));
+    assert!(!notification.matches_method("notifications/resources/list_changed"));
+
+    let notification = NotificationFromClient::InitializedNotification(None);
+    assert!(notification.matches_method("notifications/initialized"));
+}
+
+#[test]
+fn test_server_messages_ordered_for_requests() {
+    let request = |id: i64| -> ClientMessage {
+        ClientJsonrpcRequest::new(RequestId::Integer(id), RequestFromClient::PingRequest(None)).into()
+    };
+    let response =
+        |id: i64| -> ServerMessage { ServerJsonrpcResponse::new(RequestId::Integer(id), Result::default().into()).into() };
+
+    let requests = ClientMessages::Batch(vec![request(1), ClientMessage::initialized(), request(2), request(3)]);
+    let responses = ServerMessages::Batch(vec![
+        response(3),
+        response(9),
+        response(1),
+        ServerMessage::Error(JsonrpcErrorResponse::new(
+            RpcError::internal_error(),
+            Some(RequestId::Integer(2)),
+        )),
+    ]);
+
+    let ServerMessages::Batch(ordered) = responses.ordered_for(&requests) else {
+        panic!("expected a batch");
+    };
+    let ids: Vec<Option<&RequestId>> = ordered.iter().map(|message| message.request_id()).collect();
+    assert_eq!(
+        ids,
+        vec![
+            Some(&RequestId::Integer(1)),
+            Some(&RequestId::Integer(2)),
+            Some(&RequestId::Integer(3)),
+            Some(&RequestId::Integer(9))
+        ]
+    );
+    assert!(ordered[1].is_error());
+}
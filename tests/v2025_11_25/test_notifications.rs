@@ -0,0 +1,111 @@
+use rust_mcp_schema::schema_utils::*;
+use rust_mcp_schema::*;
+use serde_json::json;
+
+fn notification_method(message: ServerMessage) -> String {
+    match message {
+        ServerMessage::Notification(notification) => {
+            let value = serde_json::to_value(&notification).unwrap();
+            assert_eq!(value["jsonrpc"], json!("2.0"));
+            value["method"].as_str().unwrap().to_string()
+        }
+        _ => panic!("expected a notification"),
+    }
+}
+
+#[test]
+fn test_notify_list_changed() {
+    assert_eq!(
+        notification_method(notify_tools_changed()),
+        "notifications/tools/list_changed"
+    );
+    assert_eq!(
+        notification_method(notify_resources_changed()),
+        "notifications/resources/list_changed"
+    );
+    assert_eq!(
+        notification_method(notify_prompts_changed()),
+        "notifications/prompts/list_changed"
+    );
+}
+
+#[test]
+fn test_notify_resource_updated() {
+    let uri = "file:///project/src/main.rs";
+    let notification = ResourceUpdatedNotification::for_uri(uri);
+    assert_eq!(notification.params.uri, uri);
+
+    let message = notify_resource_updated(uri);
+    let serialized = serde_json::to_string(&message).unwrap();
+    let message: ServerMessage = serde_json::from_str(&serialized).unwrap();
+    match message {
+        ServerMessage::Notification(ServerJsonrpcNotification::ResourceUpdatedNotification(notification)) => {
+            assert_eq!(notification.method(), "notifications/resources/updated");
+            assert_eq!(notification.params.uri, uri);
+        }
+        _ => panic!("expected a ResourceUpdatedNotification"),
+    }
+}
+
+#[test]
+fn test_notification_with_id_is_rejected() {
+    use std::str::FromStr;
+
+    let message = r#"{"jsonrpc":"2.0","id":1,"method":"notifications/initialized"}"#;
+    let error = ClientJsonrpcNotification::from_str(message).unwrap_err();
+    assert_eq!(error.code, RpcError::invalid_request().code);
+    assert_eq!(error.message, "notifications must not carry an id");
+
+    let message = r#"{"jsonrpc":"2.0","id":"abc","method":"notifications/tools/list_changed"}"#;
+    let error = ServerJsonrpcNotification::from_str(message).unwrap_err();
+    assert_eq!(error.code, RpcError::invalid_request().code);
+
+    // every deserialization entry point rejects it, not only `from_str`
+    let error = serde_json::from_str::<ServerJsonrpcNotification>(message).unwrap_err();
+    assert!(error.to_string().contains("notifications must not carry an id"));
+    let value = serde_json::json!({"jsonrpc": "2.0", "id": 1, "method": "notifications/initialized"});
+    assert!(serde_json::from_value::<ClientJsonrpcNotification>(value).is_err());
+
+    // the same payload is still a valid (custom) request when parsed as a message
+    let message = ClientMessage::from_str(r#"{"jsonrpc":"2.0","id":1,"method":"notifications/initialized"}"#).unwrap();
+    assert!(message.is_request());
+}
+
+#[test]
+fn test_logging_message_notification_structured() {
+    #[derive(serde::Serialize)]
+    struct Record {
+        request_count: u32,
+        endpoint: &'static str,
+    }
+
+    let notification = LoggingMessageNotification::structured(
+        LoggingLevel::Info,
+        Some("http".to_string()),
+        &Record {
+            request_count: 3,
+            endpoint: "/mcp",
+        },
+    )
+    .unwrap();
+
+    let value = serde_json::to_value(&notification).unwrap();
+    assert_eq!(value["method"], "notifications/message");
+    assert_eq!(value["params"]["level"], "info");
+    assert_eq!(value["params"]["logger"], "http");
+    assert_eq!(value["params"]["data"], json!({ "request_count": 3, "endpoint": "/mcp" }));
+}
+
+#[test]
+fn test_progress_notification_message() {
+    let notification = ProgressNotification::for_token(ProgressToken::String("upload".to_string()), 50.0, Some(100.0), None)
+        .with_message("Uploading chunk 5 of 10".to_string());
+    let value = serde_json::to_value(&notification).unwrap();
+    assert_eq!(value["params"]["message"], "Uploading chunk 5 of 10");
+    assert_eq!(value["params"]["progressToken"], "upload");
+    assert_eq!(value["params"]["progress"], 50.0);
+
+    let notification = ProgressNotification::for_token(ProgressToken::Integer(1), 1.0, None, None);
+    let value = serde_json::to_value(&notification).unwrap();
+    assert!(value["params"].get("message").is_none());
+}
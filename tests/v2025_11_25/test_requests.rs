@@ -0,0 +1,533 @@
+use rust_mcp_schema::schema_utils::*;
+use rust_mcp_schema::*;
+use serde_json::{json, Value};
+
+#[test]
+fn test_with_progress_token_call_tool_request() {
+    let request = RequestFromClient::CallToolRequest(CallToolRequestParams::new("add_numbers"));
+    assert!(request.progress_token().is_none());
+
+    let request = request.with_progress_token(ProgressToken::String("token-1".to_string()));
+    assert!(matches!(
+        request.progress_token(),
+        Some(ProgressToken::String(token)) if token == "token-1"
+    ));
+
+    // the token survives wrapping into a jsonrpc request
+    let message = ClientJsonrpcRequest::new(RequestId::Integer(1), request);
+    let value = serde_json::to_value(&message).unwrap();
+    assert_eq!(value["params"]["_meta"]["progressToken"], json!("token-1"));
+    assert_eq!(value["params"]["name"], json!("add_numbers"));
+}
+
+#[test]
+fn test_with_progress_token_optional_params() {
+    let request = RequestFromClient::ListToolsRequest(None).with_progress_token(ProgressToken::Integer(7));
+    assert!(matches!(request, RequestFromClient::ListToolsRequest(Some(_))));
+    assert!(matches!(request.progress_token(), Some(ProgressToken::Integer(7))));
+}
+
+#[test]
+fn test_with_progress_token_custom_request() {
+    let request = RequestFromClient::CustomRequest(CustomRequest {
+        method: "custom/method".to_string(),
+        params: None,
+    })
+    .with_progress_token(ProgressToken::Integer(42));
+
+    assert!(matches!(request.progress_token(), Some(ProgressToken::Integer(42))));
+    assert!(matches!(&request, RequestFromClient::CustomRequest(custom)
+        if custom.params.as_ref().unwrap()["_meta"]["progressToken"] == json!(42)));
+}
+
+#[test]
+fn test_extend_meta_typed_params() {
+    let mut request = RequestFromClient::GetPromptRequest(GetPromptRequestParams {
+        arguments: None,
+        meta: None,
+        name: "greeting".to_string(),
+    });
+    let entries = json!({ "progressToken": "token-2", "traceparent": "00-abc-01" });
+    request.extend_meta(entries.as_object().unwrap());
+
+    let RequestFromClient::GetPromptRequest(params) = &request else {
+        panic!("Expected a GetPromptRequest");
+    };
+    let meta = params.meta.as_ref().unwrap();
+    assert!(matches!(&meta.progress_token, Some(ProgressToken::String(token)) if token == "token-2"));
+    assert_eq!(meta.extra.as_ref().unwrap()["traceparent"], json!("00-abc-01"));
+    assert_eq!(Value::Object(request.meta().unwrap()), entries);
+
+    // requests without a `_meta` field are left unchanged
+    let mut request = RequestFromClient::GetTaskRequest(GetTaskParams {
+        task_id: "task-1".to_string(),
+    });
+    request.extend_meta(entries.as_object().unwrap());
+    assert!(request.meta().is_none());
+}
+
+#[test]
+fn test_copy_meta_traceparent() {
+    let traceparent = "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01";
+    let request: ClientJsonrpcRequest = serde_json::from_value(json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "tools/call",
+        "params": {
+            "name": "add_numbers",
+            "_meta": { "traceparent": traceparent }
+        }
+    }))
+    .unwrap();
+    let request: RequestFromClient = request.into();
+
+    let mut result: ResultFromServer = CallToolResult::text_content(vec!["3".into()]).into();
+    assert!(result.meta().is_none());
+
+    copy_meta(&request, &mut result);
+
+    assert_eq!(result.meta().unwrap()["traceparent"], json!(traceparent));
+    let response = ServerJsonrpcResponse::new(RequestId::Integer(1), result);
+    let value = serde_json::to_value(&response).unwrap();
+    assert_eq!(value["result"]["_meta"]["traceparent"], json!(traceparent));
+    assert_eq!(value["result"]["content"][0]["text"], json!("3"));
+}
+
+#[test]
+fn test_copy_meta_without_request_meta() {
+    let request = RequestFromClient::ListToolsRequest(None);
+    let mut result: ResultFromServer = ListToolsResult {
+        meta: None,
+        next_cursor: None,
+        tools: vec![],
+    }
+    .into();
+
+    copy_meta(&request, &mut result);
+    assert!(result.meta().is_none());
+}
+
+#[test]
+fn test_jsonrpc_id_and_method_accessors() {
+    let request = ClientJsonrpcRequest::new(RequestId::Integer(3), RequestFromClient::PingRequest(None));
+    assert_eq!(request.id(), &RequestId::Integer(3));
+    assert_eq!(request.clone_id(), RequestId::Integer(3));
+    assert_eq!(request.method(), "ping");
+
+    let request = ServerJsonrpcRequest::new(
+        RequestId::String("abc".to_string()),
+        RequestFromServer::ListRootsRequest(None),
+    );
+    assert_eq!(request.id(), &RequestId::String("abc".to_string()));
+    assert_eq!(request.clone_id(), RequestId::String("abc".to_string()));
+    assert_eq!(request.method(), "roots/list");
+
+    let response = ServerJsonrpcResponse::new(RequestId::Integer(4), Result::default().into());
+    assert_eq!(response.id(), &RequestId::Integer(4));
+    assert_eq!(response.clone_id(), RequestId::Integer(4));
+
+    let response = ClientJsonrpcResponse::new(RequestId::Integer(5), Result::default().into());
+    assert_eq!(response.id(), &RequestId::Integer(5));
+    assert_eq!(response.clone_id(), RequestId::Integer(5));
+}
+
+#[test]
+fn test_params_value() {
+    let message = json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "tools/call",
+        "params": {
+            "name": "add_numbers",
+            "arguments": { "a": 1, "b": 2 },
+            "_meta": { "progressToken": "abc" }
+        }
+    });
+    let request: ClientJsonrpcRequest = serde_json::from_value(message.clone()).unwrap();
+    assert_eq!(request.params_value(), message["params"]);
+
+    let message = json!({
+        "jsonrpc": "2.0",
+        "id": 2,
+        "method": "custom/method",
+        "params": { "key": "value" }
+    });
+    let request: ClientJsonrpcRequest = serde_json::from_value(message.clone()).unwrap();
+    assert!(matches!(request, ClientJsonrpcRequest::CustomRequest(_)));
+    assert_eq!(request.params_value(), message["params"]);
+
+    let request = ClientJsonrpcRequest::new(RequestId::Integer(3), RequestFromClient::PingRequest(None));
+    assert!(request.params_value().is_null());
+}
+
+#[test]
+fn test_client_message_dispatch_key() {
+    let message: ClientMessage =
+        ClientJsonrpcRequest::new(RequestId::Integer(1), RequestFromClient::PingRequest(None)).into();
+    assert_eq!(message.dispatch_key(), (Some("ping"), true));
+
+    let message: ClientMessage =
+        ClientJsonrpcNotification::new(NotificationFromClient::InitializedNotification(None)).into();
+    assert_eq!(message.dispatch_key(), (Some("notifications/initialized"), false));
+
+    let message: ClientMessage = ClientJsonrpcResponse::new(RequestId::Integer(2), Result::default().into()).into();
+    assert_eq!(message.dispatch_key(), (None, true));
+}
+
+fn add_numbers_request(arguments: serde_json::Value) -> CallToolRequest {
+    let arguments = arguments.as_object().unwrap().clone();
+    CallToolRequest::new(
+        RequestId::Integer(1),
+        CallToolRequestParams::new("add_numbers").with_arguments(arguments),
+    )
+}
+
+#[test]
+fn test_call_tool_request_argument() {
+    let request = add_numbers_request(json!({ "a": 1, "b": 2, "label": "sum" }));
+
+    assert_eq!(request.argument::<i64>("a").unwrap(), 1);
+    assert_eq!(request.argument::<String>("label").unwrap(), "sum");
+
+    let error = request.argument::<i64>("c").unwrap_err();
+    assert_eq!(error.code, RpcError::invalid_params().code);
+    assert!(error.message.contains("'c'"));
+
+    let error = request.argument::<i64>("label").unwrap_err();
+    assert_eq!(error.code, RpcError::invalid_params().code);
+    assert!(error.message.contains("'label'"));
+}
+
+#[test]
+fn test_call_tool_request_arguments() {
+    #[derive(serde::Deserialize, Debug)]
+    struct AddNumbers {
+        a: i64,
+        b: i64,
+    }
+
+    let request = add_numbers_request(json!({ "a": 1, "b": 2 }));
+    let arguments: AddNumbers = request.arguments().unwrap();
+    assert_eq!(arguments.a + arguments.b, 3);
+
+    let request = add_numbers_request(json!({ "a": 1 }));
+    let error = request.arguments::<AddNumbers>().unwrap_err();
+    assert_eq!(error.code, RpcError::invalid_params().code);
+    assert!(error.message.contains("add_numbers"));
+}
+
+#[test]
+fn test_call_tool_request_require_arguments() {
+    let request = add_numbers_request(json!({ "a": 1, "b": 2 }));
+    assert!(request.has_argument("a"));
+    assert!(!request.has_argument("label"));
+    assert!(request.require_arguments(&["a", "b"]).is_ok());
+
+    let request = add_numbers_request(json!({ "b": 2 }));
+    let error = request.require_arguments(&["a", "b", "label"]).unwrap_err();
+    assert_eq!(error.code, RpcError::invalid_params().code);
+    assert_eq!(error.message, "Missing arguments: a, label");
+    assert_eq!(error.data, Some(json!({ "missing": ["a", "label"] })));
+}
+
+#[test]
+fn test_request_with_null_params() {
+    let message = r#"{"jsonrpc":"2.0","id":1,"method":"tools/list","params":null}"#;
+    let request: ClientJsonrpcRequest = serde_json::from_str(message).unwrap();
+    assert!(matches!(request, ClientJsonrpcRequest::ListToolsRequest(_)));
+
+    let message: ClientMessage = serde_json::from_str(message).unwrap();
+    assert!(matches!(
+        message,
+        ClientMessage::Request(ClientJsonrpcRequest::ListToolsRequest(_))
+    ));
+
+    let message = r#"{"jsonrpc":"2.0","method":"notifications/initialized","params":null}"#;
+    let notification: ClientJsonrpcNotification = serde_json::from_str(message).unwrap();
+    assert!(matches!(notification, ClientJsonrpcNotification::InitializedNotification(_)));
+
+    // null and absent params are handled alike, without substituting a value the peer did not send
+    let with_null: ClientJsonrpcRequest =
+        serde_json::from_str(r#"{"jsonrpc":"2.0","id":2,"method":"acme/run","params":null}"#).unwrap();
+    let without: ClientJsonrpcRequest = serde_json::from_str(r#"{"jsonrpc":"2.0","id":2,"method":"acme/run"}"#).unwrap();
+    assert_eq!(
+        serde_json::to_value(&with_null).unwrap(),
+        serde_json::to_value(&without).unwrap()
+    );
+    assert!(matches!(with_null, ClientJsonrpcRequest::CustomRequest(ref request) if request.params.is_none()));
+}
+
+#[test]
+fn test_optional_params_requests_without_params() {
+    assert_eq!(
+        serde_json::to_value(ListToolsRequest::without_params(RequestId::Integer(3))).unwrap(),
+        serde_json::to_value(ListToolsRequest::new(RequestId::Integer(3), None)).unwrap()
+    );
+    assert_eq!(
+        serde_json::to_value(PingRequest::without_params(RequestId::Integer(7))).unwrap(),
+        json!({"jsonrpc": "2.0", "id": 7, "method": "ping"})
+    );
+    assert!(ListPromptsRequest::without_params(RequestId::Integer(1)).params.is_none());
+    assert!(ListResourcesRequest::without_params(RequestId::String("abc".to_string()))
+        .params
+        .is_none());
+}
+
+#[test]
+fn test_optional_params_requests_default() {
+    assert_eq!(
+        serde_json::to_value(ListToolsRequest::default()).unwrap(),
+        serde_json::to_value(ListToolsRequest::new(RequestId::Integer(0), None)).unwrap()
+    );
+    assert_eq!(
+        serde_json::to_value(PingRequest::default()).unwrap(),
+        json!({"jsonrpc": "2.0", "id": 0, "method": "ping"})
+    );
+    assert!(ListPromptsRequest::default().params.is_none());
+    assert!(ListResourcesRequest::default().params.is_none());
+}
+
+#[test]
+fn test_request_and_notification_display() {
+    assert_eq!(
+        ClientRequest::PingRequest(PingRequest::without_params(RequestId::Integer(1))).to_string(),
+        "ping"
+    );
+    let request = ClientRequest::CallToolRequest(CallToolRequest::new(
+        RequestId::Integer(1),
+        CallToolRequestParams::new("add_numbers"),
+    ));
+    assert_eq!(request.to_string(), "tools/call");
+    assert_eq!(
+        ServerRequest::ListRootsRequest(ListRootsRequest::new(RequestId::Integer(1), None)).to_string(),
+        "roots/list"
+    );
+    assert_eq!(
+        ClientNotification::InitializedNotification(InitializedNotification::new(None)).to_string(),
+        "notifications/initialized"
+    );
+    assert_eq!(
+        ServerNotification::ToolListChangedNotification(ToolListChangedNotification::new(None)).to_string(),
+        "notifications/tools/list_changed"
+    );
+}
+
+#[test]
+fn test_request_routing_predicates() {
+    let request = RequestFromClient::CallToolRequest(CallToolRequestParams::new("add"));
+    assert!(request.matches_method("tools/call"));
+    assert!(!request.matches_method("tools/list"));
+    assert!(request.is_for_tool("add"));
+    assert!(!request.is_for_tool("sub"));
+
+    let jsonrpc_request = CallToolRequest::new(RequestId::Integer(1), CallToolRequestParams::new("add"));
+    assert!(jsonrpc_request.is_for_tool("add"));
+    assert!(!jsonrpc_request.is_for_tool("sub"));
+
+    let request = RequestFromClient::ListToolsRequest(None);
+    assert!(request.matches_method("tools/list"));
+    assert!(!request.is_for_tool("add"));
+
+    let request = RequestFromServer::ListRootsRequest(None);
+    assert!(request.matches_method("roots/list"));
+}
+
+#[test]
+fn test_request_from_method_and_params() {
+    let request = RequestFromClient::from_method_and_params(
+        "tools/call",
+        serde_json::json!({ "name": "add", "arguments": { "a": 1 } }),
+    )
+    .unwrap();
+    assert!(request.is_for_tool("add"));
+
+    let request = RequestFromClient::from_method_and_params("ping", serde_json::Value::Null).unwrap();
+    assert!(matches!(request, RequestFromClient::PingRequest(None)));
+
+    let error = RequestFromClient::from_method_and_params("tools/call", serde_json::json!({ "arguments": {} })).unwrap_err();
+    assert_eq!(error.code, RpcError::invalid_params().code);
+
+    let request = RequestFromClient::from_method_and_params("custom/echo", serde_json::json!({ "text": "hi" })).unwrap();
+    assert!(matches!(&request, RequestFromClient::CustomRequest(custom) if custom.params.as_ref().unwrap()["text"] == "hi"));
+    assert_eq!(request.method(), "custom/echo");
+}
+
+#[test]
+fn test_call_tool_request_timeout_hint() {
+    let request = CallToolRequest::new(RequestId::Integer(1), CallToolRequestParams::new("long_job"));
+    assert_eq!(request.timeout_hint(), None);
+
+    let request = request.with_timeout_hint(std::time::Duration::from_secs(5));
+    let value = serde_json::to_value(&request).unwrap();
+    assert_eq!(value["params"]["_meta"]["timeoutMs"], 5000);
+
+    let request: CallToolRequest = serde_json::from_value(value).unwrap();
+    assert_eq!(request.timeout_hint(), Some(std::time::Duration::from_secs(5)));
+}
+
+#[test]
+fn test_request_id_loose_eq() {
+    let string_id = RequestId::String("42".to_string());
+    assert_ne!(string_id, RequestId::Integer(42));
+    assert!(string_id.loose_eq(&RequestId::Integer(42)));
+    assert!(RequestId::Integer(42).loose_eq(&string_id));
+    assert_eq!(string_id.normalized(), RequestId::Integer(42));
+    assert_eq!(
+        RequestId::String(u64::MAX.to_string()).normalized(),
+        RequestId::String(u64::MAX.to_string())
+    );
+
+    assert!(!RequestId::String("042".to_string()).loose_eq(&RequestId::Integer(42)));
+    assert!(!RequestId::String("abc".to_string()).loose_eq(&RequestId::Integer(42)));
+    assert!(RequestId::String("abc".to_string()).loose_eq(&RequestId::String("abc".to_string())));
+}
+
+#[test]
+fn test_client_request_method_name() {
+    let cases = [
+        (
+            "initialize",
+            json!({"protocolVersion": "2025-11-25", "capabilities": {}, "clientInfo": {"name": "c", "version": "1"}}),
+        ),
+        ("ping", json!({})),
+        ("resources/list", json!({})),
+        ("resources/templates/list", json!({})),
+        ("resources/read", json!({"uri": "file:///a"})),
+        ("resources/subscribe", json!({"uri": "file:///a"})),
+        ("resources/unsubscribe", json!({"uri": "file:///a"})),
+        ("prompts/list", json!({})),
+        ("prompts/get", json!({"name": "p"})),
+        ("tools/list", json!({})),
+        ("tools/call", json!({"name": "t"})),
+        ("tasks/get", json!({"taskId": "t1"})),
+        ("tasks/result", json!({"taskId": "t1"})),
+        ("tasks/cancel", json!({"taskId": "t1"})),
+        ("tasks/list", json!({})),
+        ("logging/setLevel", json!({"level": "info"})),
+        (
+            "completion/complete",
+            json!({"ref": {"type": "ref/prompt", "name": "p"}, "argument": {"name": "a", "value": "v"}}),
+        ),
+    ];
+
+    for (method, params) in cases {
+        let request: ClientRequest =
+            serde_json::from_value(json!({"jsonrpc": "2.0", "id": 1, "method": method, "params": params})).unwrap();
+        let name: &'static str = (&request).into();
+        assert_eq!(name, method);
+        assert_eq!(request.method_name(), request.method());
+    }
+}
+
+#[test]
+fn test_request_id_display() {
+    assert_eq!(RequestId::Integer(-7).to_string(), "-7");
+    assert_eq!(RequestId::Integer(i64::MAX).to_string(), i64::MAX.to_string());
+    assert_eq!(RequestId::String("abc-1".to_string()).to_string(), "abc-1");
+    assert_eq!(
+        format!("handling request {}", RequestId::String("42".to_string())),
+        "handling request 42"
+    );
+}
+
+#[test]
+fn test_custom_request_builder() {
+    let request = ClientJsonrpcRequest::custom(RequestId::Integer(5), "experimental/sum")
+        .param("x", 1)
+        .param("y", 2)
+        .build();
+    assert_eq!(request.method(), "experimental/sum");
+    assert_eq!(
+        serde_json::to_value(&request).unwrap(),
+        json!({"jsonrpc": "2.0", "id": 5, "method": "experimental/sum", "params": {"x": 1, "y": 2}})
+    );
+
+    let request = ServerJsonrpcRequest::custom(RequestId::String("s-1".to_string()), "experimental/ping").build();
+    assert_eq!(
+        serde_json::to_value(&request).unwrap(),
+        json!({"jsonrpc": "2.0", "id": "s-1", "method": "experimental/ping"})
+    );
+}
+
+#[test]
+fn test_client_request_hash_ignores_id_and_key_order() {
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::{Hash, Hasher};
+
+    fn hash_of<T: Hash>(value: &T) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        value.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    let parse = |value: serde_json::Value| serde_json::from_value::<ClientRequest>(value).unwrap();
+    let first = parse(json!({"jsonrpc": "2.0", "id": 1, "method": "tools/call",
+        "params": {"name": "add", "arguments": {"a": 1, "b": 2}}}));
+    let second = parse(json!({"jsonrpc": "2.0", "id": 2, "method": "tools/call",
+        "params": {"arguments": {"b": 2, "a": 1}, "name": "add"}}));
+    assert_eq!(first, second);
+    assert_eq!(hash_of(&first), hash_of(&second));
+
+    let different = parse(json!({"jsonrpc": "2.0", "id": 1, "method": "tools/call",
+        "params": {"name": "add", "arguments": {"a": 1, "b": 3}}}));
+    assert_ne!(first, different);
+    assert_ne!(hash_of(&first), hash_of(&different));
+
+    let mut in_flight = std::collections::HashSet::new();
+    assert!(in_flight.insert(first));
+    assert!(!in_flight.insert(second));
+    assert!(in_flight.insert(different));
+}
+
+#[test]
+fn test_complete_request_references() {
+    let prompt_ref = CompleteRequestRef::prompt("code_review");
+    assert_eq!(
+        serde_json::to_value(&prompt_ref).unwrap(),
+        json!({"type": "ref/prompt", "name": "code_review"})
+    );
+
+    let resource_ref = CompleteRequestRef::resource("file:///{path}");
+    assert_eq!(
+        serde_json::to_value(&resource_ref).unwrap(),
+        json!({"type": "ref/resource", "uri": "file:///{path}"})
+    );
+
+    let request = CompleteRequest::new(
+        RequestId::Integer(1),
+        CompleteRequestParams {
+            argument: CompleteRequestArgument {
+                name: "path".to_string(),
+                value: "src/".to_string(),
+            },
+            context: None,
+            meta: None,
+            ref_: resource_ref,
+        },
+    );
+    match request.reference() {
+        CompleteRequestRef::ResourceTemplateReference(reference) => assert_eq!(reference.uri, "file:///{path}"),
+        CompleteRequestRef::PromptReference(_) => panic!("expected a resource reference"),
+    }
+}
+
+#[test]
+fn test_request_from_client_visit() {
+    let method_of = |request: &RequestFromClient| -> String {
+        request.visit(
+            |standard| format!("standard:{}", standard.method()),
+            |custom| format!("custom:{}", custom.method),
+        )
+    };
+
+    assert_eq!(method_of(&RequestFromClient::ListToolsRequest(None)), "standard:tools/list");
+
+    let custom = RequestFromClient::CustomRequest(CustomRequest {
+        method: "acme/reindex".to_string(),
+        params: None,
+    });
+    assert_eq!(method_of(&custom), "custom:acme/reindex");
+
+    let notification = NotificationFromClient::InitializedNotification(None);
+    assert!(notification.visit(|_| true, |_| false));
+}
@@ -0,0 +1,277 @@
+use rust_mcp_schema::schema_utils::*;
+use rust_mcp_schema::*;
+use serde_json::json;
+
+#[test]
+fn test_result_with_extra() {
+    let mut extra = serde_json::Map::new();
+    extra.insert("foo".to_string(), json!(1));
+    let result = Result::with_extra(extra);
+
+    let value = serde_json::to_value(&result).unwrap();
+    assert_eq!(value, json!({"foo": 1}));
+
+    let result: Result = serde_json::from_value(value).unwrap();
+    assert_eq!(result.extra.unwrap()["foo"], json!(1));
+
+    // extra attributes survive wrapping into a jsonrpc response
+    let response = ServerJsonrpcResponse::new(RequestId::Integer(1), Result::with_extra(serde_json::Map::new()).into());
+    let value = serde_json::to_value(&response).unwrap();
+    assert_eq!(value["result"], json!({}));
+}
+
+#[test]
+fn test_complete_result_from_all_without_truncation() {
+    let values: Vec<String> = (0..50).map(|i| format!("value-{i}")).collect();
+    let result = CompleteResult::from_all(values.clone());
+
+    assert_eq!(result.completion.values, values);
+    assert_eq!(result.completion.total, Some(50));
+    assert_eq!(result.completion.has_more, Some(false));
+}
+
+#[test]
+fn test_complete_result_from_all_with_truncation() {
+    let values: Vec<String> = (0..150).map(|i| format!("value-{i}")).collect();
+    let result = CompleteResult::from_all(values.clone());
+
+    assert_eq!(result.completion.values.len(), CompleteResult::MAX_VALUES);
+    assert_eq!(result.completion.values[..], values[..100]);
+    assert_eq!(result.completion.total, Some(150));
+    assert_eq!(result.completion.has_more, Some(true));
+
+    let value = serde_json::to_value(&result).unwrap();
+    assert_eq!(value["completion"]["hasMore"], json!(true));
+    assert_eq!(value["completion"]["total"], json!(150));
+}
+
+#[test]
+fn test_try_from_server_result() {
+    let server_result = ServerResult::ListToolsResult(ListToolsResult {
+        meta: None,
+        next_cursor: None,
+        tools: vec![],
+    });
+    let result: ListToolsResult = server_result.try_into().unwrap();
+    assert!(result.tools.is_empty());
+
+    let server_result = ServerResult::CompleteResult(CompleteResult::from_all(vec![]));
+    let error = CallToolResult::try_from(server_result).unwrap_err();
+    assert_eq!(error.code, RpcError::internal_error().code);
+    assert_eq!(error.message, "Not a CallToolResult");
+}
+
+#[test]
+fn test_call_tool_result_with_is_error() {
+    let mut structured_content = serde_json::Map::new();
+    structured_content.insert("reason".to_string(), json!("quota exceeded"));
+
+    let result = CallToolResult::text_content(vec!["partial output".into()])
+        .with_structured_content(structured_content)
+        .with_is_error(true);
+
+    assert_eq!(result.is_error, Some(true));
+    assert_eq!(result.content.len(), 1);
+    assert!(matches!(&result.content[0], ContentBlock::TextContent(text) if text.text == "partial output"));
+    assert_eq!(result.structured_content.as_ref().unwrap()["reason"], json!("quota exceeded"));
+
+    let result = result.with_is_error(false);
+    assert_eq!(result.is_error, Some(false));
+}
+
+#[test]
+fn test_create_message_result_assistant_text() {
+    let result = CreateMessageResult::assistant_text("test-model", "Hello there", Some("endTurn".to_string()));
+
+    assert!(matches!(result.role, Role::Assistant));
+    assert_eq!(result.model, "test-model");
+    assert_eq!(result.stop_reason.as_deref(), Some("endTurn"));
+    assert!(matches!(&result.content, CreateMessageContent::TextContent(text) if text.text == "Hello there"));
+
+    let value = serde_json::to_value(&result).unwrap();
+    assert_eq!(value["role"], json!("assistant"));
+    assert_eq!(value["content"]["type"], json!("text"));
+}
+
+#[test]
+fn test_result_types_with_meta() {
+    let meta = json!({ "traceId": "abc" }).as_object().cloned();
+
+    let result = ListToolsResult {
+        meta: None,
+        next_cursor: None,
+        tools: vec![],
+    }
+    .with_meta(meta.clone());
+    assert_eq!(serde_json::to_value(&result).unwrap()["_meta"], json!({ "traceId": "abc" }));
+
+    let result = ReadResourceResult {
+        contents: vec![],
+        meta: None,
+    }
+    .with_meta(meta);
+    assert_eq!(result.meta.as_ref().unwrap()["traceId"], "abc");
+
+    let result = result.with_meta(None);
+    assert!(result.meta.is_none());
+}
+
+#[test]
+fn test_result_none_fields_are_not_serialized() {
+    let result = ListToolsResult {
+        meta: None,
+        next_cursor: None,
+        tools: vec![],
+    };
+    assert_eq!(serde_json::to_value(&result).unwrap(), json!({ "tools": [] }));
+
+    let message = ResponseBuilder::new(RequestId::Integer(1)).result(result);
+    let value = message.to_value().unwrap();
+    assert_eq!(value["result"], json!({ "tools": [] }));
+
+    let result = CallToolResult::text_content(vec![TextContent::from("done")]);
+    let value = serde_json::to_value(&result).unwrap();
+    assert!(value.get("_meta").is_none());
+    assert!(value.get("structuredContent").is_none());
+    assert!(value["content"][0].get("annotations").is_none());
+    assert!(value["content"][0].get("_meta").is_none());
+}
+
+#[test]
+fn test_get_prompt_result_render_text() {
+    let result = GetPromptResult {
+        description: None,
+        messages: vec![
+            PromptMessage {
+                content: TextContent::from("Review this code").into(),
+                role: Role::User,
+            },
+            PromptMessage {
+                content: ContentBlock::image_content("aGVsbG8=".to_string(), "image/png".to_string()),
+                role: Role::User,
+            },
+            PromptMessage {
+                content: TextContent::from("Looks good to me").into(),
+                role: Role::Assistant,
+            },
+        ],
+        meta: None,
+    };
+
+    assert_eq!(result.messages[0].as_text(), Some("Review this code"));
+    assert_eq!(result.messages[1].as_text(), None);
+    assert_eq!(result.render_text(), "user: Review this code\nassistant: Looks good to me");
+}
+
+#[test]
+fn test_create_message_result_stop_reason_kind() {
+    let mut result: CreateMessageResult = serde_json::from_value(json!({
+        "content": {"type": "text", "text": "hi"},
+        "model": "test-model",
+        "role": "assistant",
+        "stopReason": "maxTokens"
+    }))
+    .unwrap();
+    assert_eq!(result.stop_reason_kind(), Some(StopReason::MaxTokens));
+    assert_eq!(StopReason::MaxTokens.as_str(), "maxTokens");
+    assert_eq!("toolUse".parse::<StopReason>().unwrap(), StopReason::ToolUse);
+
+    result.stop_reason = Some("contentFilter".to_string());
+    assert_eq!(result.stop_reason_kind(), None);
+    assert_eq!(result.stop_reason.as_deref(), Some("contentFilter"));
+    assert!("contentFilter".parse::<StopReason>().is_err());
+
+    result.stop_reason = None;
+    assert_eq!(result.stop_reason_kind(), None);
+}
+
+#[test]
+fn test_list_results_find() {
+    let resources: ListResourcesResult = serde_json::from_value(json!({
+        "resources": [
+            {"name": "notes", "uri": "file:///notes.txt"},
+            {"name": "logo", "uri": "file:///logo.png"}
+        ]
+    }))
+    .unwrap();
+    assert_eq!(
+        resources.find_by_uri("file:///logo.png").map(|r| r.name.as_str()),
+        Some("logo")
+    );
+    assert!(resources.find_by_uri("file:///missing.txt").is_none());
+
+    let prompts: ListPromptsResult = serde_json::from_value(json!({
+        "prompts": [{"name": "summarize"}, {"name": "translate"}]
+    }))
+    .unwrap();
+    assert_eq!(prompts.find_by_name("translate").map(|p| p.name.as_str()), Some("translate"));
+    assert!(prompts.find_by_name("review").is_none());
+
+    let tools: ListToolsResult = serde_json::from_value(json!({
+        "tools": [{"name": "add", "inputSchema": {"type": "object"}}]
+    }))
+    .unwrap();
+    assert!(tools.find_by_name("add").is_some());
+    assert!(tools.find_by_name("subtract").is_none());
+}
+
+#[test]
+fn test_prompt_and_resource_builders() {
+    let prompt = Prompt::builder("summarize")
+        .description("Summarizes a document")
+        .argument(PromptArgument {
+            description: None,
+            name: "document".to_string(),
+            required: Some(true),
+            title: None,
+        })
+        .build();
+    assert_eq!(prompt.name, "summarize");
+    assert_eq!(prompt.arguments.len(), 1);
+    assert_eq!(prompt.arguments[0].name, "document");
+    assert!(prompt.title.is_none());
+
+    let resource = Resource::builder("file:///notes.txt", "notes")
+        .mime_type("text/plain")
+        .size(12)
+        .build();
+    assert_eq!(resource.uri, "file:///notes.txt");
+    assert_eq!(resource.name, "notes");
+    assert_eq!(resource.mime_type.as_deref(), Some("text/plain"));
+    assert_eq!(resource.size, Some(12));
+    assert_eq!(
+        serde_json::to_value(&resource).unwrap(),
+        json!({"uri": "file:///notes.txt", "name": "notes", "mimeType": "text/plain", "size": 12})
+    );
+}
+
+#[test]
+fn test_server_result_kind() {
+    let server_result = ServerResult::ListToolsResult(ListToolsResult {
+        meta: None,
+        next_cursor: None,
+        tools: vec![],
+    });
+    assert_eq!(server_result.kind(), ServerResultKind::ListToolsResult);
+
+    let server_result = ServerResult::CallToolResult(CallToolResult::text_content(vec!["3".into()]));
+    assert_eq!(server_result.kind(), ServerResultKind::CallToolResult);
+}
+
+#[test]
+fn test_call_tool_result_capped() {
+    let content = vec![ContentBlock::text_content("small".to_string())];
+    let result = CallToolResult::capped(content, 1024);
+    assert_eq!(result.content[0].as_text_content().unwrap().text, "small");
+    assert_eq!(result.is_error, None);
+
+    let content = vec![
+        ContentBlock::text_content("é".repeat(100)),
+        ContentBlock::image_content("aGVsbG8=".to_string(), "image/png".to_string()),
+    ];
+    let result = CallToolResult::capped(content, 51);
+    assert_eq!(result.is_error, Some(true));
+    assert_eq!(result.content.len(), 1);
+    let text = &result.content[0].as_text_content().unwrap().text;
+    assert_eq!(text, &"é".repeat(25));
+}
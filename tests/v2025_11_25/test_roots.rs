@@ -0,0 +1,44 @@
+use rust_mcp_schema::*;
+use std::path::{Path, PathBuf};
+
+#[cfg(unix)]
+#[test]
+fn test_root_from_path_round_trip() {
+    let path = Path::new("/home/user/projects/mcp");
+    let root = Root::from_path(path, Some("mcp".to_string())).unwrap();
+    assert_eq!(root.uri, "file:///home/user/projects/mcp");
+    assert_eq!(root.name.as_deref(), Some("mcp"));
+    assert_eq!(root.as_path(), Some(path.to_path_buf()));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_root_from_path_with_spaces() {
+    let path = Path::new("/home/user/My Projects/café #1");
+    let root = Root::from_path(path, None).unwrap();
+    assert_eq!(root.uri, "file:///home/user/My%20Projects/caf%C3%A9%20%231");
+    assert_eq!(root.as_path(), Some(path.to_path_buf()));
+}
+
+#[test]
+fn test_root_from_relative_path_fails() {
+    let error = Root::from_path(Path::new("relative/path"), None).unwrap_err();
+    assert_eq!(error.code, RpcError::invalid_params().code);
+}
+
+#[test]
+fn test_root_as_path() {
+    let mut root = Root::from_path(&std::env::current_dir().unwrap(), None).unwrap();
+
+    root.uri = "file://localhost/tmp/a%20b".to_string();
+    assert_eq!(root.as_path(), Some(PathBuf::from("/tmp/a b")));
+
+    root.uri = "https://example.com/tmp".to_string();
+    assert_eq!(root.as_path(), None);
+
+    root.uri = "file://server/share".to_string();
+    assert_eq!(root.as_path(), None);
+
+    root.uri = "file:///tmp/%zz".to_string();
+    assert_eq!(root.as_path(), None);
+}
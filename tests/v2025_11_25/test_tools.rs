@@ -0,0 +1,115 @@
+use rust_mcp_schema::schema_utils::*;
+use rust_mcp_schema::*;
+use serde_json::json;
+
+#[test]
+fn test_tool_annotations_builder() {
+    let annotations = ToolAnnotations::builder().title("Read file").read_only(true).build();
+    assert_eq!(
+        serde_json::to_value(&annotations).unwrap(),
+        json!({ "title": "Read file", "readOnlyHint": true })
+    );
+
+    let annotations = ToolAnnotationsBuilder::new()
+        .destructive(false)
+        .idempotent(true)
+        .open_world(false)
+        .build();
+    assert_eq!(
+        serde_json::to_value(&annotations).unwrap(),
+        json!({ "destructiveHint": false, "idempotentHint": true, "openWorldHint": false })
+    );
+}
+
+#[test]
+fn test_tool_call_respond_uses_request_id() {
+    let request = CallToolRequest::new(RequestId::String("call-7".to_string()), CallToolRequestParams::new("add"));
+    let call = ToolCall::new(request);
+    assert_eq!(call.tool_name(), "add");
+    assert_eq!(call.id(), &RequestId::String("call-7".to_string()));
+
+    let response = call.respond(CallToolResult::text_content(vec![TextContent::new(
+        "3".to_string(),
+        None,
+        None,
+    )]));
+    assert!(matches!(response, ServerMessage::Response(_)));
+    assert_eq!(response.request_id(), Some(&RequestId::String("call-7".to_string())));
+
+    let call: ToolCall = CallToolRequest::new(RequestId::Integer(8), CallToolRequestParams::new("fail")).into();
+    let response = call.respond(CallToolError::unknown_tool("fail"));
+    let value = serde_json::to_value(&response).unwrap();
+    assert_eq!(value["id"], 8);
+    assert_eq!(value["result"]["isError"], true);
+}
+
+#[test]
+fn test_call_tool_result_content_type_counts() {
+    let link = ResourceLink::new(
+        vec![],
+        "notes".to_string(),
+        "file:///notes.txt".to_string(),
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+    );
+    let result = CallToolResult::from_content(vec![
+        ContentBlock::text_content("one".to_string()),
+        ContentBlock::text_content("two".to_string()),
+        ContentBlock::image_content("aGVsbG8=".to_string(), "image/png".to_string()),
+        ContentBlock::resource_link(link),
+    ]);
+    let counts = result.content_type_counts();
+    assert_eq!(counts.get("text"), Some(&2));
+    assert_eq!(counts.get("image"), Some(&1));
+    assert_eq!(counts.get("resource_link"), Some(&1));
+    assert_eq!(counts.get("audio"), None);
+    assert_eq!(counts.values().sum::<usize>(), result.content.len());
+    for content in &result.content {
+        assert!(counts.contains_key(content.content_type()));
+    }
+}
+
+#[test]
+fn test_tool_builder() {
+    let tool = Tool::builder(
+        "add",
+        ToolInputSchema::new(vec!["a".to_string(), "b".to_string()], None, None),
+    )
+    .title("Add numbers")
+    .description("Adds two numbers")
+    .annotations(ToolAnnotations::builder().read_only(true).build())
+    .build();
+    assert_eq!(tool.name, "add");
+    assert_eq!(tool.input_schema.required, vec!["a", "b"]);
+    assert_eq!(tool.title.as_deref(), Some("Add numbers"));
+    assert_eq!(tool.description.as_deref(), Some("Adds two numbers"));
+    assert!(tool.output_schema.is_none());
+
+    let value = serde_json::to_value(&tool).unwrap();
+    assert_eq!(value["inputSchema"]["type"], "object");
+    assert_eq!(value["annotations"], json!({"readOnlyHint": true}));
+}
+
+#[test]
+fn test_call_tool_result_structured_get() {
+    let structured = json!({
+        "result": {"items": [{"id": 1}, {"id": 2}], "total": 2},
+        "a/b": {"~key": true}
+    });
+    let result = CallToolResult::from_content(vec![]).with_structured_content(structured.as_object().unwrap().clone());
+
+    assert_eq!(result.structured_get("/result/items/0"), Some(&json!({"id": 1})));
+    assert_eq!(result.structured_get("/result/items/1/id"), Some(&json!(2)));
+    assert_eq!(result.structured_get("/result/total"), Some(&json!(2)));
+    assert_eq!(result.structured_get("/a~1b/~0key"), Some(&json!(true)));
+    assert_eq!(result.structured_get("/result/items/5"), None);
+    assert_eq!(result.structured_get("/missing"), None);
+    assert_eq!(result.structured_get("result"), None);
+    assert_eq!(result.structured_get(""), None);
+
+    assert_eq!(CallToolResult::from_content(vec![]).structured_get("/result"), None);
+}